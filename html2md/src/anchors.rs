@@ -1,5 +1,6 @@
 use super::TagHandler;
 use super::StructuredPrinter;
+use super::Flavor;
 
 use markup5ever_rcdom::{Handle,NodeData};
 
@@ -29,8 +30,66 @@ impl TagHandler for AnchorHandler {
     }
 
     fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        if printer.options.references {
+            let url = self.url.trim();
+            if url.is_empty() {
+                return;
+            }
+
+            let number = match printer.references.iter().position(|reference| reference == url) {
+                Some(index) => index + 1,
+                None => {
+                    printer.references.push(url.to_string());
+                    printer.references.len()
+                }
+            };
+            printer.append_str(&format!(" [{}]", number));
+            return;
+        }
+
+        if !printer.options.links {
+            return;
+        }
+
+        if printer.options.flavor == Flavor::Gfm {
+            if let Some(autolink) = autolink(&printer.data[self.start_pos..], &self.url) {
+                printer.data.truncate(self.start_pos);
+                printer.append_str(&autolink);
+                return;
+            }
+        }
+
         // add braces around already present text, put an url afterwards
-        //printer.insert_str(self.start_pos, "[");
-        //printer.append_str(&format!("]({})", self.url))
+        printer.insert_str(self.start_pos, "[");
+        printer.append_str(&format!("]({})", self.url))
     }
-}
\ No newline at end of file
+}
+
+/// GFM autolink for links whose text is their own URL or e-mail address
+fn autolink(text: &str, url: &str) -> Option<String> {
+    if url.is_empty() || url.contains(|ch: char| ch.is_whitespace() || ch == '<' || ch == '>') {
+        return None;
+    }
+
+    // text was escaped on the way in
+    let text = text.replace('\\', "");
+    let has_scheme = ["http://", "https://", "ftp://"].iter().any(|scheme| url.starts_with(scheme));
+    if has_scheme && text == url {
+        return Some(format!("<{}>", url));
+    }
+    if url.strip_prefix("mailto:") == Some(text.as_str()) && text.contains('@') {
+        return Some(format!("<{}>", text));
+    }
+
+    return None;
+}
+
+/// Appends the list of numbered references at the end of the document, if links were rendered as references
+pub(crate) fn append_references(printer: &mut StructuredPrinter) {
+    if printer.references.is_empty() {
+        return;
+    }
+
+    let list: Vec<String> = printer.references.iter().enumerate().map(|(index, url)| format!("{}. {}", index + 1, url)).collect();
+    printer.append_str(&format!("\n\nReferences\n\n{}\n", list.join("\n")));
+}
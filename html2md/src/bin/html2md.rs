@@ -2,11 +2,25 @@ extern crate html2md;
 
 use std::io::{self, Read};
 
+use html2md::ConversionOptions;
+
 fn main() {
+    // `--gfm` for GitHub-Flavored Markdown, `--plain` for text structure only,
+    // `--references` for numbered link references in either
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut options = if args.iter().any(|arg| arg == "--gfm") {
+        ConversionOptions::gfm()
+    } else if args.iter().any(|arg| arg == "--plain") {
+        ConversionOptions::plain_text()
+    } else {
+        ConversionOptions::markdown()
+    };
+    options.references = args.iter().any(|arg| arg == "--references");
+
     let stdin = io::stdin();
     let mut buffer = String::new();
     let mut handle = stdin.lock();
 
     handle.read_to_string(&mut buffer).expect("Must be readable HTML!");
-    println!("{}", html2md::parse_html(&buffer));
-}
\ No newline at end of file
+    println!("{}", html2md::parse_html_with_options(&buffer, &options));
+}
@@ -1,11 +1,16 @@
 use super::TagHandler;
 use super::StructuredPrinter;
+use super::Flavor;
+
+use crate::common::{get_tag_attr, text_content};
 
 use markup5ever_rcdom::{Handle,NodeData};
 
 #[derive(Default)]
 pub struct CodeHandler {
-    code_type: String
+    code_type: String,
+    /// Opening code fence of a `pre` block, with its info string in GFM
+    fence: String
 }
 
 impl CodeHandler {
@@ -23,13 +28,16 @@ impl CodeHandler {
                 // code block should have its own paragraph
                 if start {
                     printer.insert_newline();
+                    printer.append_str(&format!("\n{}\n", self.fence));
+                } else {
+                    let fence = self.fence.trim_end_matches(|ch: char| ch != '`');
+                    printer.append_str(&format!("\n{}\n", fence));
                 }
-                printer.append_str("\n```\n");
                 if !start {
                     printer.insert_newline();
                 }
             },
-            "code" | "samp" => printer.append_str("`"),
+            "code" | "samp" | "kbd" => printer.append_str("`"),
             _ => {}
         }
     }
@@ -43,9 +51,32 @@ impl TagHandler for CodeHandler {
             _ => String::new()
         };
 
+        self.fence = String::from("```");
+        if self.code_type == "pre" && printer.options.flavor == Flavor::Gfm {
+            // fence must be longer than any backtick run of the code
+            let longest_run = text_content(tag, &[], false).split(|ch: char| ch != '`').map(|run| run.len()).max().unwrap_or(0);
+            self.fence = "`".repeat(std::cmp::max(3, longest_run + 1));
+            if let Some(language) = language(tag) {
+                self.fence.push_str(&language);
+            }
+        }
+
         self.do_handle(printer, true);
     }
     fn after_handle(&mut self, printer: &mut StructuredPrinter) {
         self.do_handle(printer, false);
     }
 }
+
+/// Language from a `language-xxx` class of the `pre` tag or of its `code` child
+fn language(tag: &Handle) -> Option<String> {
+    let code_children = tag.children.borrow().iter()
+        .filter(|child| matches!(child.data, NodeData::Element { ref name, .. } if &name.local == "code"))
+        .cloned()
+        .collect::<Vec<Handle>>();
+
+    return std::iter::once(tag).chain(code_children.iter())
+        .filter_map(|candidate| get_tag_attr(candidate, "class"))
+        .flat_map(|class| class.split_whitespace().map(str::to_string).collect::<Vec<String>>())
+        .find_map(|class| class.strip_prefix("language-").filter(|language| !language.is_empty()).map(str::to_string));
+}
@@ -9,4 +9,42 @@ pub fn get_tag_attr(tag: &Handle, attr_name: &str) -> Option<String> {
         }
         _ => return None
     }
-}
\ No newline at end of file
+}
+/// Parent node of the tag, `None` for the document
+pub fn parent(tag: &Handle) -> Option<Handle> {
+    let weak = tag.parent.take();
+    let parent = weak.as_ref().and_then(|weak| weak.upgrade());
+    tag.parent.set(weak);
+    return parent;
+}
+
+/// Name of the element, empty for other nodes
+pub fn tag_name(tag: &Handle) -> String {
+    return match tag.data {
+        NodeData::Element { ref name, .. } => name.local.to_string(),
+        _ => String::new()
+    };
+}
+
+/// Text of the tag and its descendants, leaving out the elements named in `skipped`.
+/// Whitespace is collapsed when `collapse` is set, otherwise the text is only trimmed
+pub fn text_content(tag: &Handle, skipped: &[&str], collapse: bool) -> String {
+    fn gather(node: &Handle, skipped: &[&str], text: &mut String) {
+        match node.data {
+            NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+            NodeData::Element { ref name, .. } if skipped.contains(&name.local.as_ref()) => {}
+            _ => {
+                for child in node.children.borrow().iter() {
+                    gather(child, skipped, text);
+                }
+            }
+        }
+    }
+
+    let mut text = String::new();
+    gather(tag, skipped, &mut text);
+    if collapse {
+        return text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    }
+    return text.trim().to_string();
+}
@@ -1,14 +1,25 @@
 use super::TagHandler;
 use super::StructuredPrinter;
+use super::styles::strengthen;
 
-use markup5ever_rcdom::Handle;
+use markup5ever_rcdom::{Handle,NodeData};
 
+/// Handler for block-level elements without Markdown syntax of their own, their content
+/// is separated from the surrounding text
 #[derive(Default)]
-pub struct ContainerHandler;
+pub struct ContainerHandler {
+    skip: bool
+}
 
 impl TagHandler for ContainerHandler {
 
-    fn handle(&mut self, _tag: &Handle, printer: &mut StructuredPrinter) {
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        // navigation, sidebars, forms and footers are mostly boilerplate
+        self.skip = match tag.data {
+            NodeData::Element { ref name, .. } => printer.options.drop_boilerplate && matches!(&*name.local, "nav" | "aside" | "form" | "footer"),
+            _ => false
+        };
+
         printer.insert_newline();
         printer.insert_newline();
     }
@@ -17,4 +28,39 @@ impl TagHandler for ContainerHandler {
         printer.insert_newline();
         printer.insert_newline();
     }
-}
\ No newline at end of file
+
+    fn skip_descendants(&self) -> bool {
+        return self.skip;
+    }
+}
+
+/// Handler for disclosure widgets: the summary is a strong paragraph followed by the details,
+/// all of them are shown
+#[derive(Default)]
+pub struct DetailsHandler {
+    start_pos: usize,
+    is_summary: bool
+}
+
+impl TagHandler for DetailsHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        self.is_summary = match tag.data {
+            NodeData::Element { ref name, .. } => &name.local == "summary",
+            _ => false
+        };
+
+        printer.insert_newline();
+        printer.insert_newline();
+        self.start_pos = printer.data.len();
+    }
+
+    fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        if self.is_summary {
+            strengthen(printer, self.start_pos);
+        }
+
+        printer.insert_newline();
+        printer.insert_newline();
+    }
+}
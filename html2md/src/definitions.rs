@@ -0,0 +1,37 @@
+use super::TagHandler;
+use super::StructuredPrinter;
+use super::styles::strengthen;
+
+use markup5ever_rcdom::{Handle,NodeData};
+
+/// Handler for definition lists: Markdown has no syntax for them, so terms and definitions
+/// become paragraphs of their own, terms are strong
+#[derive(Default)]
+pub struct DefinitionListHandler {
+    start_pos: usize,
+    tag_name: String
+}
+
+impl TagHandler for DefinitionListHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        self.tag_name = match tag.data {
+            NodeData::Element { ref name, .. } => name.local.to_string(),
+            _ => String::new()
+        };
+
+        // every part of the list is a block
+        printer.insert_newline();
+        printer.insert_newline();
+        self.start_pos = printer.data.len();
+    }
+
+    fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        if self.tag_name == "dt" {
+            strengthen(printer, self.start_pos);
+        }
+
+        printer.insert_newline();
+        printer.insert_newline();
+    }
+}
@@ -0,0 +1,438 @@
+use lazy_static::lazy_static;
+
+use super::TagHandler;
+use super::TagHandlerFactory;
+use super::StructuredPrinter;
+use super::EmbedMode;
+use super::escape_markdown;
+
+use crate::common::{get_tag_attr, text_content};
+use crate::dummy::IdentityHandler;
+use crate::quotes::QuoteHandler;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use regex::Regex;
+use markup5ever_rcdom::{Handle,NodeData};
+
+lazy_static! {
+    /// Pattern that detects iframes with Youtube embedded videos<br/>
+    /// Examples:
+    /// * `https://www.youtube.com/embed/zE-dmXZp3nU?wmode=opaque`
+    /// * `https://www.youtube-nocookie.com/embed/5yo6exIypkY`
+    /// * `https://www.youtube.com/embed/TXm6IXrbQuM`
+    static ref YOUTUBE_PATTERN : Regex = Regex::new(r"www\.youtube(?:-nocookie)?\.com/embed/([-\w]+)").unwrap();
+
+    /// Pattern that detects iframes with Vimeo embedded videos<br/>
+    /// Example: `https://player.vimeo.com/video/76979871?h=8272103f6e`
+    static ref VIMEO_PATTERN: Regex = Regex::new(r"player\.vimeo\.com/video/(\d+)").unwrap();
+
+    /// Pattern that detects iframes with Instagram embedded photos<br/>
+    /// Examples:
+    /// * `https://www.instagram.com/p/B1BKr9Wo8YX/embed/`
+    /// * `https://www.instagram.com/p/BpKjlo-B4uI/embed/`
+    static ref INSTAGRAM_PATTERN: Regex = Regex::new(r"www\.instagram\.com/p/([-\w]+)/embed").unwrap();
+
+    /// Pattern that detects iframes and links of Twitter and X posts<br/>
+    /// Examples:
+    /// * `https://platform.twitter.com/embed/Tweet.html?id=1400000000000000000`
+    /// * `https://twitter.com/jack/status/20?ref_src=twsrc%5Etfw`
+    static ref TWITTER_PATTERN: Regex = Regex::new(r"^https?://(?:www\.|mobile\.)?(?:platform\.twitter\.com/embed/Tweet\.html\?(?:.*&)?id=|(?:twitter|x)\.com/\w+/status(?:es)?/)(\d+)").unwrap();
+
+    /// Pattern that detects iframes with Spotify players<br/>
+    /// Example: `https://open.spotify.com/embed/track/4uLU6hMCjMI75M1A2tKUQC?utm_source=generator`
+    static ref SPOTIFY_PATTERN: Regex = Regex::new(r"open\.spotify\.com/embed/(track|album|playlist|episode|show|artist)/(\w+)").unwrap();
+
+    /// Pattern that detects iframes with SoundCloud players, the URL of the track is a query parameter<br/>
+    /// Example: `https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/293&auto_play=false`
+    static ref SOUNDCLOUD_PATTERN: Regex = Regex::new(r"w\.soundcloud\.com/player/?\?(?:.*&)?url=([^&]+)").unwrap();
+
+    /// Pattern that detects iframes with CodePen pens<br/>
+    /// Example: `https://codepen.io/team/codepen/embed/preview/PNaGbb?default-tab=result`
+    static ref CODEPEN_PATTERN: Regex = Regex::new(r"codepen\.io/([-\w]+(?:/[-\w]+)?)/embed/(?:preview/)?(\w+)").unwrap();
+
+    /// Patter that detects iframes with VKontakte embedded videos<br/>
+    /// Examples:
+    /// * `https://vk.com/video_ext.php?oid=-49423435&id=456245092&hash=e1611aefe899c4f8`
+    /// * `https://vk.com/video_ext.php?oid=-76477496&id=456239454&hash=ebfdc2d386617b97`
+    static ref VK_PATTERN: Regex = Regex::new(r"vk\.com/video_ext\.php\?oid=(-?\d+)&id=(\d+)&hash=(.*)").unwrap();
+
+    static ref YANDEX_MUSIC_TRACK_PATTERN: Regex = Regex::new(r"https://music.yandex.ru/iframe/#track/(\d+)/(\d+)").unwrap();
+    static ref YANDEX_MUSIC_ALBUM_PATTERN: Regex = Regex::new(r"https://music.yandex.ru/iframe/#album/(\d+)").unwrap();
+}
+
+/// Media embedded in the page, as recognized by an `EmbedProvider`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Embed {
+    /// Service name: `YouTube`
+    pub provider: String,
+    /// Kind of media, lowercase: `video`, `post`, `track`
+    pub kind: String,
+    /// Canonical URL of the media
+    pub url: String,
+    /// Title of the media, such as the `title` of the iframe or the text of a post
+    pub title: Option<String>,
+    /// Preview image shown in link cards
+    pub thumbnail: Option<String>,
+}
+
+impl Embed {
+
+    /// Embed without title and thumbnail
+    pub fn new(provider: &str, kind: &str, url: String) -> Embed {
+        Embed { provider: provider.to_string(), kind: kind.to_string(), url, title: None, thumbnail: None }
+    }
+
+    /// Markdown link to the media, with its preview image when there's one
+    pub fn card(&self) -> String {
+        let label = format!("Embedded {} {}", self.provider, self.kind);
+        return match &self.thumbnail {
+            Some(thumbnail) => format!("[![{}]({})]({})", label, thumbnail, self.url),
+            None => format!("[{}]({})", label, self.url)
+        };
+    }
+
+    /// Text standing for the media: `[Video: title]`, or the provider name if there's no title
+    pub fn placeholder(&self) -> String {
+        let mut kind = self.kind.clone();
+        if let Some(first) = kind.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        return format!("[{}: {}]", kind, self.title.as_deref().unwrap_or(&self.provider));
+    }
+}
+
+/// Recognizes media of a service among `iframe` and `blockquote` elements
+pub trait EmbedProvider {
+    /// Returns the embedded media if `tag` is one of this service
+    fn detect(&self, tag: &Handle) -> Option<Embed>;
+}
+
+/// Ordered list of embed providers, the first one recognizing an element wins.
+///
+/// It's a factory for `iframe` and `blockquote` handlers, so custom providers are used
+/// by passing it to `parse_html_custom`:
+/// ```
+/// # use std::collections::HashMap;
+/// # use html2md::{parse_html_custom, ConversionOptions, Handle};
+/// # use html2md::embeds::{Embed, EmbedProvider, EmbedRegistry};
+/// struct Dailymotion;
+///
+/// impl EmbedProvider for Dailymotion {
+///     fn detect(&self, tag: &Handle) -> Option<Embed> {
+///         let src = html2md::common::get_tag_attr(tag, "src")?;
+///         let id = src.strip_prefix("https://www.dailymotion.com/embed/video/")?;
+///         Some(Embed::new("Dailymotion", "video", format!("https://www.dailymotion.com/video/{}", id)))
+///     }
+/// }
+///
+/// let mut registry = EmbedRegistry::default();
+/// registry.register(Dailymotion);
+/// let mut custom = HashMap::new();
+/// registry.install(&mut custom);
+/// let md = parse_html_custom("<iframe src='https://www.dailymotion.com/embed/video/x7tgad0'></iframe>", &custom, &ConversionOptions::markdown());
+/// assert_eq!(md, "[Embedded Dailymotion video](https://www.dailymotion.com/video/x7tgad0)");
+/// ```
+#[derive(Clone)]
+pub struct EmbedRegistry {
+    providers: Vec<Rc<dyn EmbedProvider>>
+}
+
+impl EmbedRegistry {
+
+    /// Registry without any provider
+    pub fn empty() -> EmbedRegistry {
+        EmbedRegistry { providers: vec![] }
+    }
+
+    /// Adds a provider, it's tried after the ones already registered
+    pub fn register<P: EmbedProvider + 'static>(&mut self, provider: P) {
+        self.providers.push(Rc::new(provider));
+    }
+
+    /// Returns the embedded media of `tag`, if any provider recognizes it
+    pub fn detect(&self, tag: &Handle) -> Option<Embed> {
+        return self.providers.iter().find_map(|provider| provider.detect(tag));
+    }
+
+    /// Registers this registry as the handler factory of `iframe` and `blockquote` tags
+    pub fn install(&self, custom: &mut HashMap<String, Box<dyn TagHandlerFactory>>) {
+        custom.insert(String::from("iframe"), Box::new(self.clone()));
+        custom.insert(String::from("blockquote"), Box::new(self.clone()));
+    }
+}
+
+impl Default for EmbedRegistry {
+
+    /// Registry with the built-in providers: YouTube, Vimeo, Twitter, Instagram, Spotify,
+    /// SoundCloud, CodePen, VK and Yandex Music
+    fn default() -> Self {
+        let mut registry = EmbedRegistry::empty();
+        registry.register(YouTube);
+        registry.register(Vimeo);
+        registry.register(Twitter);
+        registry.register(Instagram);
+        registry.register(Spotify);
+        registry.register(SoundCloud);
+        registry.register(CodePen);
+        registry.register(Vk);
+        registry.register(YandexMusic);
+        return registry;
+    }
+}
+
+impl TagHandlerFactory for EmbedRegistry {
+    fn instantiate(&self) -> Box<dyn TagHandler> {
+        return Box::new(EmbedHandler { registry: self.clone(), fallback: None });
+    }
+}
+
+/// Handler for `iframe` and `blockquote` elements: recognized media are rendered as the embed mode says,
+/// other blockquotes are quotes and other iframes are kept as HTML in link cards mode
+#[derive(Default)]
+pub struct EmbedHandler {
+    registry: EmbedRegistry,
+    fallback: Option<Box<dyn TagHandler>>
+}
+
+impl TagHandler for EmbedHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        let embed = self.registry.detect(tag);
+        let is_iframe = matches!(tag.data, NodeData::Element { ref name, .. } if &name.local == "iframe");
+        if embed.is_none() && !is_iframe {
+            // just a quote
+            let mut fallback: Box<dyn TagHandler> = Box::new(QuoteHandler::default());
+            fallback.handle(tag, printer);
+            self.fallback = Some(fallback);
+            return;
+        }
+
+        printer.insert_newline();
+        printer.insert_newline();
+        match (embed, &printer.options.embeds) {
+            (Some(embed), EmbedMode::Card) => printer.append_str(&embed.card()),
+            (Some(embed), EmbedMode::Placeholder) => {
+                let placeholder = escape_markdown(printer, &embed.placeholder());
+                printer.append_str(&placeholder);
+            }
+            (None, EmbedMode::Card) => {
+                // not found, use generic implementation
+                let mut identity = IdentityHandler::default();
+                identity.handle(tag, printer);
+            }
+            _ => {}
+        }
+    }
+
+    fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.after_handle(printer);
+            return;
+        }
+
+        printer.insert_newline();
+        printer.insert_newline();
+    }
+
+    fn skip_descendants(&self) -> bool {
+        return self.fallback.is_none();
+    }
+}
+
+/// `src` of an iframe, `None` for other elements
+fn iframe_src(tag: &Handle) -> Option<String> {
+    return match tag.data {
+        NodeData::Element { ref name, .. } if &name.local == "iframe" => get_tag_attr(tag, "src"),
+        _ => None
+    };
+}
+
+/// `title` of the iframe, if it's meaningful
+fn iframe_title(tag: &Handle) -> Option<String> {
+    return get_tag_attr(tag, "title")
+        .map(|title| title.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|title| !title.is_empty() && !title.to_lowercase().contains("player"));
+}
+
+/// Whether the blockquote has `class` among its classes
+fn has_class(tag: &Handle, class: &str) -> bool {
+    let is_blockquote = matches!(tag.data, NodeData::Element { ref name, .. } if &name.local == "blockquote");
+    return is_blockquote && get_tag_attr(tag, "class").is_some_and(|classes| classes.split_whitespace().any(|candidate| candidate == class));
+}
+
+/// Captures of `pattern` in the iframe `src`
+fn capture(tag: &Handle, pattern: &Regex) -> Option<Vec<String>> {
+    let src = iframe_src(tag)?;
+    let captures = pattern.captures(&src)?;
+    return Some(captures.iter().skip(1).map(|group| group.map_or(String::new(), |group| group.as_str().to_string())).collect());
+}
+
+/// `href` of links among descendants of the element
+fn links(tag: &Handle) -> Vec<String> {
+    let mut result = vec![];
+    if let NodeData::Element { ref name, .. } = tag.data {
+        if &name.local == "a" {
+            result.extend(get_tag_attr(tag, "href"));
+        }
+    }
+    for child in tag.children.borrow().iter() {
+        result.extend(links(child));
+    }
+    return result;
+}
+
+/// Decodes `%XX` escapes of a query parameter
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = match (bytes[index], value.get(index + 1..index + 3)) {
+            (b'%', Some(hex)) => u8::from_str_radix(hex, 16).ok(),
+            _ => None
+        };
+        match escaped {
+            Some(byte) => { decoded.push(byte); index += 3; }
+            None => { decoded.push(bytes[index]); index += 1; }
+        }
+    }
+    return String::from_utf8_lossy(&decoded).into_owned();
+}
+
+/// YouTube videos, with their preview image
+pub struct YouTube;
+
+impl EmbedProvider for YouTube {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let media_id = capture(tag, &YOUTUBE_PATTERN)?.remove(0);
+        return Some(Embed {
+            title: iframe_title(tag),
+            thumbnail: Some(format!("https://img.youtube.com/vi/{}/0.jpg", media_id)),
+            ..Embed::new("YouTube", "video", format!("https://www.youtube.com/watch?v={}", media_id))
+        });
+    }
+}
+
+/// Vimeo videos
+pub struct Vimeo;
+
+impl EmbedProvider for Vimeo {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let video_id = capture(tag, &VIMEO_PATTERN)?.remove(0);
+        return Some(Embed { title: iframe_title(tag), ..Embed::new("Vimeo", "video", format!("https://vimeo.com/{}", video_id)) });
+    }
+}
+
+/// Twitter and X posts, both `blockquote.twitter-tweet` embeds and their iframes
+pub struct Twitter;
+
+impl EmbedProvider for Twitter {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        if let Some(mut captures) = capture(tag, &TWITTER_PATTERN) {
+            return Some(Embed::new("Twitter", "post", status_url(&captures.remove(0))));
+        }
+        if !has_class(tag, "twitter-tweet") {
+            return None;
+        }
+
+        // the post text is in the first paragraph, followed by the author and a link to the post
+        let post_id = links(tag).into_iter().rev().find_map(|href| Some(TWITTER_PATTERN.captures(&href)?[1].to_string()))?;
+        let paragraph = tag.children.borrow().iter()
+            .find(|child| matches!(child.data, NodeData::Element { ref name, .. } if &name.local == "p"))
+            .cloned();
+        let title = paragraph.map(|paragraph| text_content(&paragraph, &[], true)).filter(|text| !text.is_empty());
+        return Some(Embed { title, ..Embed::new("Twitter", "post", status_url(&post_id)) });
+    }
+}
+
+/// Canonical URL of a Twitter post, without the tracking parameters of embed links
+fn status_url(post_id: &str) -> String {
+    return format!("https://twitter.com/i/status/{}", post_id);
+}
+
+/// Instagram posts, both iframes and `blockquote.instagram-media` embeds
+pub struct Instagram;
+
+impl EmbedProvider for Instagram {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        if let Some(mut captures) = capture(tag, &INSTAGRAM_PATTERN) {
+            let media_id = captures.remove(0);
+            return Some(Embed {
+                thumbnail: Some(format!("https://www.instagram.com/p/{}/media/?size=m", media_id)),
+                ..Embed::new("Instagram", "post", format!("https://www.instagram.com/p/{}/embed/", media_id))
+            });
+        }
+        if !has_class(tag, "instagram-media") {
+            return None;
+        }
+
+        let url = get_tag_attr(tag, "data-instgrm-permalink")?;
+        return Some(Embed::new("Instagram", "post", url.split('?').next().unwrap_or_default().to_string()));
+    }
+}
+
+/// Spotify tracks, albums, playlists, podcast episodes and shows
+pub struct Spotify;
+
+impl EmbedProvider for Spotify {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let captures = capture(tag, &SPOTIFY_PATTERN)?;
+        let (kind, id) = (&captures[0], &captures[1]);
+        return Some(Embed { title: iframe_title(tag), ..Embed::new("Spotify", kind, format!("https://open.spotify.com/{}/{}", kind, id)) });
+    }
+}
+
+/// SoundCloud tracks and playlists
+pub struct SoundCloud;
+
+impl EmbedProvider for SoundCloud {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let url = percent_decode(&capture(tag, &SOUNDCLOUD_PATTERN)?.remove(0));
+        let kind = if url.contains("/playlists/") { "playlist" } else { "track" };
+        return Some(Embed { title: iframe_title(tag), ..Embed::new("SoundCloud", kind, url) });
+    }
+}
+
+/// CodePen pens
+pub struct CodePen;
+
+impl EmbedProvider for CodePen {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let captures = capture(tag, &CODEPEN_PATTERN)?;
+        let (user, pen) = (&captures[0], &captures[1]);
+        return Some(Embed { title: iframe_title(tag), ..Embed::new("CodePen", "pen", format!("https://codepen.io/{}/pen/{}", user, pen)) });
+    }
+}
+
+/// VKontakte videos
+pub struct Vk;
+
+impl EmbedProvider for Vk {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let captures = capture(tag, &VK_PATTERN)?;
+        let (owner_id, video_id) = (&captures[0], &captures[1]);
+        return Some(Embed {
+            title: iframe_title(tag),
+            thumbnail: Some(String::from("https://st.vk.com/images/icons/video_empty_2x.png")),
+            ..Embed::new("VK", "video", format!("https://vk.com/video{}_{}", owner_id, video_id))
+        });
+    }
+}
+
+/// Yandex Music tracks and albums
+pub struct YandexMusic;
+
+impl EmbedProvider for YandexMusic {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        if let Some(captures) = capture(tag, &YANDEX_MUSIC_TRACK_PATTERN) {
+            let (track_id, album_id) = (&captures[0], &captures[1]);
+            let url = format!("https://music.yandex.ru/album/{}/track/{}", album_id, track_id);
+            return Some(Embed { title: iframe_title(tag), ..Embed::new("Yandex Music", "track", url) });
+        }
+
+        let album_id = capture(tag, &YANDEX_MUSIC_ALBUM_PATTERN)?.remove(0);
+        return Some(Embed { title: iframe_title(tag), ..Embed::new("Yandex Music", "album", format!("https://music.yandex.ru/album/{}", album_id)) });
+    }
+}
@@ -0,0 +1,33 @@
+use super::TagHandler;
+use super::StructuredPrinter;
+
+use markup5ever_rcdom::{Handle,NodeData};
+
+/// Handler for figures and their captions. The caption is a paragraph of its own,
+/// dropped along with its content when captions are disabled
+#[derive(Default)]
+pub struct FigureHandler {
+    skip: bool
+}
+
+impl TagHandler for FigureHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        self.skip = match tag.data {
+            NodeData::Element { ref name, .. } => &name.local == "figcaption" && !printer.options.captions,
+            _ => false
+        };
+
+        printer.insert_newline();
+        printer.insert_newline();
+    }
+
+    fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        printer.insert_newline();
+        printer.insert_newline();
+    }
+
+    fn skip_descendants(&self) -> bool {
+        return self.skip;
+    }
+}
@@ -1,5 +1,6 @@
 use super::TagHandler;
 use super::StructuredPrinter;
+use super::Flavor;
 
 use markup5ever_rcdom::{Handle,NodeData};
 
@@ -19,7 +20,16 @@ impl TagHandler for HeaderHandler {
         printer.insert_newline();
         printer.insert_newline();
 
-        return;
+        if !printer.options.headers {
+            return;
+        }
+
+        if printer.options.flavor == Flavor::Gfm {
+            // ATX headings for all levels, `h1` is `# `
+            let level = self.header_type[1..].parse().unwrap_or(1);
+            printer.append_str(&format!("{} ", "#".repeat(level)));
+            return;
+        }
 
         match self.header_type.as_ref() {
             "h3" => printer.append_str("### "),
@@ -31,7 +41,16 @@ impl TagHandler for HeaderHandler {
     }
 
     fn after_handle(&mut self, printer: &mut StructuredPrinter) {
-        return;
+        if !printer.options.headers {
+            return;
+        }
+
+        if printer.options.flavor == Flavor::Gfm {
+            printer.insert_newline();
+            printer.insert_newline();
+            return;
+        }
+
         match self.header_type.as_ref() {
             "h1" => printer.append_str("\n==========\n"),
             "h2" => printer.append_str("\n----------\n"),
@@ -1,14 +1,31 @@
+use lazy_static::lazy_static;
+
 use super::TagHandler;
 use super::StructuredPrinter;
 
-use crate::common::get_tag_attr;
+use crate::common::{get_tag_attr, parent, tag_name};
 
 use markup5ever_rcdom::Handle;
+use regex::Regex;
 
 use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 
 const FRAGMENT: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
 
+/// Attributes lazy loading scripts keep the real image URL in, while `src` holds a placeholder
+const LAZY_ATTRIBUTES: [&str; 3] = ["data-src", "data-lazy-src", "data-original"];
+
+lazy_static! {
+    /// Pattern that detects spacer and tracking images by their file name, or inline GIFs<br/>
+    /// Examples:
+    /// * `https://example.com/images/spacer.gif`
+    /// * `/img/1x1.png?campaign=42`
+    static ref SPACER_PATTERN: Regex = Regex::new(r"(?i)^data:image/gif|/(?:spacer|blank|pixel|transparent|trans|clear|1x1|dot)\.(?:gif|png)(?:$|[?#])").unwrap();
+
+    /// Length in pixels of a CSS property, such as `width: 494px`
+    static ref STYLE_LENGTH_PATTERN: Regex = Regex::new(r"(?i)(?:^|[;\s])(width|height)\s*:\s*(\d+)(?:\.\d+)?px").unwrap();
+}
+
 /// Handler for `<img>` tag. Depending on circumstances can produce both
 /// inline HTML-formatted image and Markdown native one
 #[derive(Default)]
@@ -19,11 +36,18 @@ pub struct ImgHandler {
 impl TagHandler for ImgHandler {
 
     fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
-        return;
+        if !printer.options.images {
+            return;
+        }
+
+        if is_spacer(tag) {
+            return;
+        }
+
         // hack: detect if the image has associated style and has display in block mode
-        let style_tag = get_tag_attr(tag, "src");
+        let style_tag = get_tag_attr(tag, "style");
         if let Some(style) = style_tag {
-            if style.contains("display: block") {
+            if style.replace(' ', "").contains("display:block") {
                 self.block_mode = true
             }
         }
@@ -35,7 +59,7 @@ impl TagHandler for ImgHandler {
         }
 
         // try to extract attrs
-        let src = get_tag_attr(tag, "src");
+        let src = best_source(tag);
         let alt = get_tag_attr(tag, "alt");
         let title = get_tag_attr(tag, "title");
         let height = get_tag_attr(tag, "height");
@@ -74,4 +98,103 @@ impl TagHandler for ImgHandler {
             printer.insert_newline();
         }
     }
-}
\ No newline at end of file
+}
+/// URL a high density screen would load for `<img>`: the best `srcset` candidate of the image and
+/// the `<source>` elements of its `<picture>`, then the lazy loading attributes, then `src`.
+///
+/// Candidates are ranked by width, or by density when they have no width descriptor. When `sizes`
+/// gives the displayed width in pixels the smallest candidate covering twice that width is picked,
+/// otherwise the largest one, the first in source order among equals
+pub fn best_source(img: &Handle) -> Option<String> {
+    let mut sources = vec![img.clone()];
+    if let Some(picture) = parent(img).filter(|parent| tag_name(parent) == "picture") {
+        sources.extend(picture.children.borrow().iter().filter(|child| tag_name(child) == "source").cloned());
+    }
+
+    // displayed width in pixels, from the default entry of `sizes`
+    let displayed = get_tag_attr(img, "sizes")
+        .and_then(|sizes| sizes.rsplit(',').next().and_then(|size| size.trim().strip_suffix("px").and_then(|size| size.parse::<f32>().ok())));
+    let base = displayed.or_else(|| dimension(img, "width").map(|width| width as f32)).unwrap_or(1.0);
+
+    let mut candidates: Vec<(String, f32)> = vec![];
+    for source in &sources {
+        let srcset = get_tag_attr(source, "srcset").filter(|srcset| !srcset.trim().is_empty()).or_else(|| get_tag_attr(source, "data-srcset"));
+        for (url, width, density) in parse_srcset(&srcset.unwrap_or_default()) {
+            if !url.starts_with("data:") {
+                candidates.push((url, width.map_or(density * base, |width| width as f32)));
+            }
+        }
+    }
+
+    let covering = displayed.and_then(|displayed| candidates.iter()
+        .filter(|(_, size)| *size >= displayed * 2.0)
+        .min_by(|first, second| first.1.total_cmp(&second.1)));
+    let best = covering.or_else(|| candidates.iter().rev().max_by(|first, second| first.1.total_cmp(&second.1)));
+    if let Some((url, _)) = best {
+        return Some(url.clone());
+    }
+
+    let lazy = LAZY_ATTRIBUTES.iter().filter_map(|attr| get_tag_attr(img, attr)).find(|url| !url.trim().is_empty());
+    return lazy.or_else(|| get_tag_attr(img, "src")).map(|url| url.trim().to_string());
+}
+
+/// Candidates of a `srcset` attribute as `(url, width, density)`, following the parsing rules of HTML:
+/// URLs may contain commas, candidates with invalid descriptors are dropped
+pub fn parse_srcset(srcset: &str) -> Vec<(String, Option<u32>, f32)> {
+    let mut candidates = vec![];
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+        if rest.is_empty() {
+            return candidates;
+        }
+
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (url, after) = rest.split_at(url_end);
+        let descriptors = if url.ends_with(',') {
+            // no descriptors, the comma ends the candidate
+            rest = after;
+            ""
+        } else {
+            let end = after.find(',').unwrap_or(after.len());
+            rest = &after[end..];
+            &after[..end]
+        };
+        let url = url.trim_end_matches(',');
+
+        let mut width = None;
+        let mut density = None;
+        let mut valid = true;
+        for descriptor in descriptors.split_whitespace() {
+            let (value, unit) = descriptor.split_at(descriptor.char_indices().last().map_or(0, |(index, _)| index));
+            let parsed = match unit {
+                "w" if width.is_none() && density.is_none() => { width = value.parse::<u32>().ok().filter(|width| *width > 0); width.is_some() }
+                "x" if width.is_none() && density.is_none() => { density = value.parse::<f32>().ok().filter(|density| *density > 0.0); density.is_some() }
+                _ => false
+            };
+            valid = valid && parsed;
+        }
+        if valid && !url.is_empty() {
+            candidates.push((url.to_string(), width, density.unwrap_or(1.0)));
+        }
+    }
+}
+
+/// Width or height of the image in pixels, from its attribute or its inline style
+pub fn dimension(img: &Handle, name: &str) -> Option<u32> {
+    let attribute = get_tag_attr(img, name).and_then(|value| value.trim().trim_end_matches("px").parse::<u32>().ok());
+    return attribute.or_else(|| {
+        let style = get_tag_attr(img, "style")?;
+        let length = STYLE_LENGTH_PATTERN.captures_iter(&style).find(|capture| capture[1].eq_ignore_ascii_case(name))?;
+        return length[2].parse::<u32>().ok();
+    });
+}
+
+/// Whether the image is a tracking pixel or a spacer: at most 2 pixels wide or high,
+/// hidden, or a known spacer file
+pub fn is_spacer(img: &Handle) -> bool {
+    let tiny = |name: &str| dimension(img, name).is_some_and(|length| length <= 2);
+    let hidden = get_tag_attr(img, "style").is_some_and(|style| style.replace(' ', "").contains("display:none"));
+    let spacer_url = best_source(img).is_some_and(|url| SPACER_PATTERN.is_match(&url));
+    return tiny("width") || tiny("height") || hidden || spacer_url;
+}
@@ -0,0 +1,66 @@
+use super::TagHandler;
+use super::StructuredPrinter;
+use super::escape_markdown;
+
+use crate::common::get_tag_attr;
+
+use markup5ever_rcdom::Handle;
+
+/// Handler for abbreviations, their title follows the text in parentheses when enabled
+#[derive(Default)]
+pub struct AbbrHandler {
+    start_pos: usize,
+    title: Option<String>
+}
+
+impl TagHandler for AbbrHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        self.start_pos = printer.data.len();
+        self.title = get_tag_attr(tag, "title")
+            .map(|title| title.split_whitespace().collect::<Vec<&str>>().join(" "))
+            .filter(|title| !title.is_empty());
+    }
+
+    fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        if !printer.options.abbreviations {
+            return;
+        }
+
+        if let Some(title) = &self.title {
+            let text = printer.data[self.start_pos..].trim();
+            if !text.is_empty() && text != title {
+                // keep the whitespace after the abbreviation outside of the expansion
+                let end = printer.data.trim_end().len();
+                let expansion = format!(" ({})", escape_markdown(printer, title));
+                printer.insert_str(end, &expansion);
+            }
+        }
+    }
+}
+
+/// Handler for dates and times, replaced by their machine-readable `datetime` when enabled
+#[derive(Default)]
+pub struct TimeHandler {
+    replaced: bool
+}
+
+impl TagHandler for TimeHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        let datetime = get_tag_attr(tag, "datetime").map(|datetime| datetime.trim().to_string()).filter(|datetime| !datetime.is_empty());
+        self.replaced = false;
+        if let Some(datetime) = datetime.filter(|_| printer.options.datetimes) {
+            printer.append_str(&datetime);
+            self.replaced = true;
+        }
+    }
+
+    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {
+
+    }
+
+    fn skip_descendants(&self) -> bool {
+        return self.replaced;
+    }
+}
@@ -27,23 +27,36 @@ pub mod codes;
 pub mod quotes;
 pub mod tables;
 pub mod containers;
-pub mod iframes;
+pub mod definitions;
+pub mod figures;
+pub mod embeds;
+pub mod inlines;
+pub mod math;
+pub mod ruby;
 
 use crate::dummy::DummyHandler;
 use crate::dummy::IdentityHandler;
 use crate::dummy::HtmlCherryPickHandler;
 use crate::paragraphs::ParagraphHandler;
-use crate::anchors::AnchorHandler;
+use crate::anchors::{AnchorHandler, append_references};
 use crate::images::ImgHandler;
 use crate::headers::HeaderHandler;
 use crate::lists::ListItemHandler;
 use crate::lists::ListHandler;
+use crate::lists::CheckboxHandler;
 use crate::styles::StyleHandler;
 use crate::codes::CodeHandler;
-use crate::quotes::QuoteHandler;
+use crate::quotes::InlineQuoteHandler;
+use crate::inlines::AbbrHandler;
+use crate::inlines::TimeHandler;
+use crate::math::MathHandler;
+use crate::ruby::RubyHandler;
 use crate::tables::TableHandler;
 use crate::containers::ContainerHandler;
-use crate::iframes::IframeHandler;
+use crate::containers::DetailsHandler;
+use crate::definitions::DefinitionListHandler;
+use crate::figures::FigureHandler;
+use crate::embeds::EmbedHandler;
 
 lazy_static! {
     static ref EXCESSIVE_WHITESPACE_PATTERN: Regex = Regex::new("\\s{2,}").unwrap();   // for HTML on-the-fly cleanup
@@ -66,20 +79,29 @@ lazy_static! {
 /// # Arguments
 /// `html` is source HTML as `String`
 /// `custom` is custom tag hadler producers for tags you want, can be empty
-pub fn parse_html_custom(html: &str, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) -> String {
+/// `options` selects the Markdown syntax to produce
+pub fn parse_html_custom(html: &str, custom: &HashMap<String, Box<dyn TagHandlerFactory>>, options: &ConversionOptions) -> String {
     let dom = parse_document(RcDom::default(), ParseOpts::default()).from_utf8().read_from(&mut html.as_bytes()).unwrap();
     let mut result = StructuredPrinter::default();
+    result.options = options.clone();
     walk(&dom.document, &mut result, custom);
+    append_references(&mut result);
 
     return clean_markdown(&result.data);
 }
 
 /// Main function of this library. Parses incoming HTML, converts it into Markdown
-/// and returns converted string.
+/// and returns converted string. Only the text structure is kept, as with `ConversionOptions::plain_text()`;
+/// use `parse_html_with_options` with `ConversionOptions::markdown()` for all supported syntax.
 /// # Arguments
 /// `html` is source HTML as `String`
 pub fn parse_html(html: &str) -> String {
-    parse_html_custom(html, &HashMap::default())
+    parse_html_with_options(html, &ConversionOptions::default())
+}
+
+/// Same as `parse_html` but produces only the Markdown syntax enabled in `options`
+pub fn parse_html_with_options(html: &str, options: &ConversionOptions) -> String {
+    parse_html_custom(html, &HashMap::default(), options)
 }
 
 /// Same as `parse_html` but retains all "span" html elements intact
@@ -95,7 +117,7 @@ pub fn parse_html_extended(html: &str) -> String {
 
     let mut tag_factory: HashMap<String, Box<dyn TagHandlerFactory>> = HashMap::new();
     tag_factory.insert(String::from("span"), Box::new(SpanAsIsTagFactory{}));
-    return parse_html_custom(html, &tag_factory);
+    return parse_html_custom(html, &tag_factory, &ConversionOptions::default());
 }
 
 /// Recursively walk through all DOM tree and handle all elements according to
@@ -106,7 +128,7 @@ pub fn parse_html_extended(html: &str) -> String {
 /// `result` is output holder with position and context tracking
 /// `custom` is custom tag hadler producers for tags you want, can be empty
 fn walk(input: &Handle, result: &mut StructuredPrinter, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) {
-    let mut handler : Box<dyn TagHandler> = Box::new(DummyHandler::default());
+    let mut handler : Box<dyn TagHandler + '_> = Box::new(DummyHandler::default());
     let mut tag_name = String::default();
     match input.data {
         NodeData::Document | NodeData::Doctype {..} | NodeData::ProcessingInstruction {..} => {},
@@ -120,12 +142,16 @@ fn walk(input: &Handle, result: &mut StructuredPrinter, custom: &HashMap<String,
                 // in case it's not just a whitespace after the newline or another whitespace
 
                 // regular text, collapse whitespace and newlines in text
-                let inside_code = result.parent_chain.iter().any(|tag| tag == "code");
+                let inside_code = result.parent_chain.iter().any(|tag| tag == "code" || tag == "samp" || tag == "kbd");
                 if !inside_code {
                     text = escape_markdown(result, &text);
                 }
                 let minified_text = EXCESSIVE_WHITESPACE_PATTERN.replace_all(&text, " ");
-                let minified_text = minified_text.trim_matches(|ch: char| ch == '\n' || ch == '\r');
+                let mut minified_text = minified_text.trim_matches(|ch: char| ch == '\n' || ch == '\r');
+                if result.data.ends_with(' ') {
+                    // whitespace is already there, e.g. after a list or task list marker
+                    minified_text = minified_text.trim_start_matches(' ');
+                }
                 result.append_str(&minified_text);
             }
         }
@@ -144,28 +170,39 @@ fn walk(input: &Handle, result: &mut StructuredPrinter, custom: &HashMap<String,
                 // no user-supplied factory, take one of built-in ones
                 handler = match tag_name.as_ref() {
                     // containers
-                    "div" | "section" | "header" | "footer" => Box::new(ContainerHandler::default()),
+                    "div" | "section" | "header" | "footer" | "article" | "main" | "aside" | "nav" | "address" | "hgroup" | "search"
+                        | "form" | "fieldset" | "legend" | "dialog" | "center" => Box::new(ContainerHandler::default()),
                     // pagination, breaks
                     "p" | "br" | "hr" => Box::new(ParagraphHandler::default()),
-                    "q" | "cite" | "blockquote" => Box::new(QuoteHandler::default()),
+                    "blockquote" => Box::new(EmbedHandler::default()),
                     // spoiler tag
-                    "details" | "summary" => Box::new(HtmlCherryPickHandler::default()),
+                    "details" | "summary" => Box::new(DetailsHandler::default()),
+                    "dl" | "dt" | "dd" => Box::new(DefinitionListHandler::default()),
+                    "figure" | "figcaption" => Box::new(FigureHandler::default()),
                     // formatting
-                    "b" | "i" | "s" | "strong" | "em" | "del" => Box::new(StyleHandler::default()),
+                    "b" | "i" | "s" | "strike" | "strong" | "em" | "del" | "cite" | "u" | "ins" | "mark" | "small" => Box::new(StyleHandler::default()),
+                    "q" => Box::new(InlineQuoteHandler::default()),
+                    "abbr" => Box::new(AbbrHandler::default()),
+                    "time" => Box::new(TimeHandler::default()),
                     "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Box::new(HeaderHandler::default()),
-                    "pre" | "code" => Box::new(CodeHandler::default()),
+                    "pre" | "code" | "samp" | "kbd" => Box::new(CodeHandler::default()),
                     // images, links
                     "img" => Box::new(ImgHandler::default()),
                     "a" => Box::new(AnchorHandler::default()),
                     // lists
                     "ol" | "ul" | "menu" => Box::new(ListHandler::default()),
                     "li" => Box::new(ListItemHandler::default()),
+                    "input" => Box::new(CheckboxHandler::default()),
                     // as-is
                     "sub" | "sup" => Box::new(IdentityHandler::default()),
                     // tables, handled fully internally as markdown can't have nested content in tables
-                    // supports only single tables as of now
-                    "table" => Box::new(TableHandler::default()),
-                    "iframe" => Box::new(IframeHandler::default()),
+                    "table" => Box::new(TableHandler::new(custom)),
+                    "iframe" => Box::new(EmbedHandler::default()),
+                    "ruby" => Box::new(RubyHandler::default()),
+                    // formulas
+                    "math" => Box::new(MathHandler::default()),
+                    "script" if math::is_tex_script(input) => Box::new(MathHandler::default()),
+                    "span" if math::is_math_rendering(input) => Box::new(MathHandler::default()),
                     // other
                     "html" | "head" | "body" => Box::new(DummyHandler::default()),
                     _ => Box::new(DummyHandler::default())
@@ -240,6 +277,136 @@ fn clean_markdown(text: &str) -> String {
     return intermediate.into_owned();
 }
 
+/// Markdown dialect of the enabled syntax
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flavor {
+    /// Markdown as written on its inception page: setext headers for `h1` and `h2`, plain code fences
+    Original,
+    /// GitHub-Flavored Markdown: ATX headings, code fences with info strings, task list items,
+    /// autolinks and pipe tables with escaped cell content
+    Gfm,
+}
+
+/// Rendering of tables
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableMode {
+    /// Pipe tables with a header row and a divider
+    Pipe,
+    /// Natural text: a sentence per row pairing header and cell texts (`Country: France; Population: 68M.`),
+    /// cell texts only when the table has no header. The caption is the lead sentence
+    Linear,
+}
+
+/// Rendering of formulas
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathMode {
+    /// `$...$` inline and `$$...$$` display LaTeX
+    Latex,
+    /// Linear text such as `(a + b)/2` or `x^2`
+    Text,
+    /// The given token in place of every formula
+    Placeholder(String),
+}
+
+/// Rendering of embedded media recognized by the embed providers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmbedMode {
+    /// Markdown link card: the preview image linked to the media, or a plain link without preview
+    Card,
+    /// Text placeholder with the kind and title of the media: `[Video: Never Gonna Give You Up]`
+    Placeholder,
+    /// Nothing
+    Skip,
+}
+
+/// Rendering of ruby annotations, such as the furigana of Japanese text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RubyMode {
+    /// Base text only: `漢字`
+    Base,
+    /// Base text followed by its reading: `漢字(かんじ)`
+    Parenthesized,
+    /// Reading in place of the annotated base text: `かんじ`
+    Reading,
+}
+
+/// Markdown syntax produced by the conversion.
+///
+/// Disabled constructs are replaced by their text: headers become paragraphs,
+/// links keep their text only, images and embedded media are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionOptions {
+    /// `Header\n===` and `### Header` syntax
+    pub headers: bool,
+    /// Bold, italic, strikethrough and underline marks
+    pub emphasis: bool,
+    /// `![alt](src)` images, or inline `<img>` tags when they have geometry attributes
+    pub images: bool,
+    /// Rendering of media embedded from other sites, such as YouTube videos or tweets
+    pub embeds: EmbedMode,
+    /// `[text](href)` links
+    pub links: bool,
+    /// Links as numbered references, whether `links` is set or not: `text [1]` in the body and a
+    /// `References` list of the URLs at the end, like `lynx -dump` prints. Repeated URLs keep their number
+    pub references: bool,
+    /// Markdown dialect of the enabled syntax
+    pub flavor: Flavor,
+    /// Rendering of tables
+    pub tables: TableMode,
+    /// Captions of figures and tables, as paragraphs of their own
+    pub captions: bool,
+    /// `1.` markers for all ordered lists. Otherwise markers follow the list `type`,
+    /// with letters and roman numerals as browsers show them
+    pub numbered_lists: bool,
+    /// Leaves out the content of `nav`, `aside`, `form` and `footer` elements, which is mostly
+    /// site navigation and chrome. Off in both presets, all content is kept
+    pub drop_boilerplate: bool,
+    /// Titles of `abbr` elements after their text: `WHO (World Health Organization)`
+    pub abbreviations: bool,
+    /// `datetime` attributes of `time` elements instead of their text
+    pub datetimes: bool,
+    /// Styles Markdown has no syntax for (`u`, `ins`, `mark` and `small`) as inline HTML tags
+    pub inline_html: bool,
+    /// Rendering of formulas
+    pub math: MathMode,
+    /// Rendering of ruby annotations
+    pub ruby: RubyMode,
+}
+
+impl ConversionOptions {
+    /// Text structure only (paragraphs, lists, quotes and code) with linearized tables, for natural language processing
+    pub fn plain_text() -> Self {
+        ConversionOptions {
+            headers: false, emphasis: false, images: false, embeds: EmbedMode::Skip, links: false, references: false, flavor: Flavor::Original, tables: TableMode::Linear,
+            captions: true, numbered_lists: false, drop_boilerplate: false,
+            abbreviations: false, datetimes: false, inline_html: false, math: MathMode::Text,
+            ruby: RubyMode::Base
+        }
+    }
+
+    /// All supported Markdown syntax
+    pub fn markdown() -> Self {
+        ConversionOptions {
+            headers: true, emphasis: true, images: true, embeds: EmbedMode::Card, links: true, references: false, flavor: Flavor::Original, tables: TableMode::Pipe,
+            captions: true, numbered_lists: true, drop_boilerplate: false,
+            abbreviations: false, datetimes: false, inline_html: false, math: MathMode::Latex,
+            ruby: RubyMode::Base
+        }
+    }
+
+    /// All supported Markdown syntax, rendering correctly on GitHub and GitLab
+    pub fn gfm() -> Self {
+        ConversionOptions { flavor: Flavor::Gfm, ..ConversionOptions::markdown() }
+    }
+}
+
+/// The plain text preset, which `parse_html` has always produced
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        ConversionOptions::plain_text()
+    }
+}
+
 /// Intermediate result of HTML -> Markdown conversion.
 ///
 /// Holds context in the form of parent tags and siblings chain
@@ -254,6 +421,12 @@ pub struct StructuredPrinter {
 
     /// resulting markdown document
     pub data: String,
+
+    /// Markdown syntax to produce
+    pub options: ConversionOptions,
+
+    /// URLs of the links rendered as numbered references so far, the first one is `[1]`
+    pub references: Vec<String>,
 }
 
 impl StructuredPrinter {
@@ -300,12 +473,38 @@ pub trait TagHandler {
 }
 
 /// FFI variant for HTML -> Markdown conversion for calling from other languages
+///
+/// Returns NULL if `html` is NULL or the conversion result can't be represented as C string.
+/// The returned string must be released with `parse_free`.
+///
+/// # Safety
+/// `html` must be NULL or point to a NUL-terminated string that stays valid during the call.
 #[no_mangle]
-pub extern fn parse(html: *const c_char) -> *const c_char {
-    let in_html = unsafe { CStr::from_ptr(html) };
+pub unsafe extern "C" fn parse(html: *const c_char) -> *mut c_char {
+    if html.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let in_html = CStr::from_ptr(html);
     let out_md = parse_html(&in_html.to_string_lossy());
 
-    CString::new(out_md).unwrap().into_raw()
+    match CString::new(out_md) {
+        Ok(md) => md.into_raw(),
+        Err(_) => std::ptr::null_mut()
+    }
+}
+
+/// Releases the string returned by `parse`. Passing NULL is a no-op.
+///
+/// # Safety
+/// `md` must be NULL or a pointer returned by `parse` that wasn't released yet.
+#[no_mangle]
+pub unsafe extern "C" fn parse_free(md: *mut c_char) {
+    if md.is_null() {
+        return;
+    }
+
+    drop(CString::from_raw(md));
 }
 
 /// Expose the JNI interface for android below
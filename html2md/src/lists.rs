@@ -1,7 +1,11 @@
 use super::TagHandler;
 use super::StructuredPrinter;
+use super::Flavor;
 
-use markup5ever_rcdom::Handle;
+use crate::common::{get_tag_attr, parent};
+
+use markup5ever_rcdom::{Handle,NodeData};
+use std::rc::Rc;
 
 /// gets all list elements registered by a `StructuredPrinter` in reverse order
 fn list_hierarchy(printer: &mut StructuredPrinter) -> Vec<&String> {
@@ -33,12 +37,13 @@ impl TagHandler for ListHandler {
 #[derive(Default)]
 pub struct ListItemHandler {
     start_pos: usize,
-    list_type: String
+    list_type: String,
+    marker: String
 }
 
 impl TagHandler for ListItemHandler {
 
-    fn handle(&mut self, _tag: &Handle, printer: &mut StructuredPrinter) {
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
         {
             let parent_lists = list_hierarchy(printer);
             let nearest_parent_list = parent_lists.first();
@@ -56,23 +61,30 @@ impl TagHandler for ListItemHandler {
             printer.insert_newline();
         }
 
-        let current_depth = printer.parent_chain.len();
-        let order = printer.siblings[&current_depth].len() + 1;
-        match self.list_type.as_ref() {
-            "ul" | "menu" => printer.append_str("* "), // unordered list: *, *, *
-            "ol" => printer.append_str(&(order.to_string() + ". ")), // ordered list: 1, 2, 3
-            _ => {} // never happens
-        }
+        self.marker = match self.list_type.as_ref() {
+            "ol" => {
+                // ordered list: 1, 2, 3 or the list type of the item when Markdown markers are disabled
+                let kind = get_tag_attr(tag, "type").or_else(|| parent(tag).and_then(|list| get_tag_attr(&list, "type")));
+                let kind = if printer.options.numbered_lists { None } else { kind };
+                let (ordinals, position) = ordinals(tag);
+                // CommonMark reads `-1.` as a bullet, lists counting below zero are numbered from 1 instead
+                let ordinal = if printer.options.numbered_lists && ordinals.iter().any(|ordinal| *ordinal < 0) {
+                    position as i64 + 1
+                } else {
+                    ordinals[position]
+                };
+                format!("{}. ", format_ordinal(ordinal, kind.as_deref()))
+            }
+            _ => String::from("* ") // unordered list: *, *, *
+        };
+        printer.append_str(&self.marker);
 
         self.start_pos = printer.data.len();
     }
 
     fn after_handle(&mut self, printer: &mut StructuredPrinter) {
-        let padding = match self.list_type.as_ref() {
-            "ul" => 2,
-            "ol" => 3,
-            _ => 4
-        };
+        // continuation lines are aligned with the text after the marker
+        let padding = self.marker.chars().count();
 
         // need to cleanup leading newlines, <p> inside <li> should produce valid 
         // list element, not an empty line
@@ -95,4 +107,113 @@ impl TagHandler for ListItemHandler {
             index -= 1;
         }
     }
-}
\ No newline at end of file
+}
+
+/// Computes the ordinal values of the items of the `<ol>` containing `item` following HTML rules:
+/// counting starts at `start`, or the item count for `reversed` lists, and continues from the `value`
+/// of items that have one. Returns them with the position of `item` among them
+fn ordinals(item: &Handle) -> (Vec<i64>, usize) {
+    let list = match parent(item) {
+        Some(list) => list,
+        None => return (vec![1], 0)
+    };
+    let items: Vec<Handle> = list.children.borrow().iter()
+        .filter(|child| matches!(child.data, NodeData::Element { ref name, .. } if &name.local == "li"))
+        .cloned()
+        .collect();
+
+    let reversed = get_tag_attr(&list, "reversed").is_some();
+    let step = if reversed { -1 } else { 1 };
+    let mut ordinal = get_tag_attr(&list, "start")
+        .and_then(|start| parse_integer(&start))
+        .unwrap_or(if reversed { items.len() as i64 } else { 1 });
+    let mut ordinals = vec![];
+    let mut position = 0;
+    for candidate in items {
+        if let Some(value) = get_tag_attr(&candidate, "value").and_then(|value| parse_integer(&value)) {
+            ordinal = value;
+        }
+        if Rc::ptr_eq(&candidate, item) {
+            position = ordinals.len();
+        }
+        ordinals.push(ordinal);
+        ordinal += step;
+    }
+
+    return (ordinals, position);
+}
+
+/// Parses `start` and `value` attributes. Like browsers, only 32-bit integers are valid,
+/// so counting from them can't overflow
+fn parse_integer(value: &str) -> Option<i64> {
+    return value.trim().parse::<i32>().ok().map(i64::from);
+}
+
+/// Formats `ordinal` as the list `type` attribute says: `a`/`A` for letters, `i`/`I` for roman numerals.
+/// Numbers outside of what these can represent, and other types, are decimal
+fn format_ordinal(ordinal: i64, kind: Option<&str>) -> String {
+    let formatted = match kind {
+        Some("a") | Some("A") if ordinal > 0 => {
+            // bijective base 26: a..z, aa..az, ba..
+            let mut letters = vec![];
+            let mut rest = ordinal;
+            while rest > 0 {
+                rest -= 1;
+                letters.push((b'a' + (rest % 26) as u8) as char);
+                rest /= 26;
+            }
+            letters.iter().rev().collect()
+        }
+        Some("i") | Some("I") if ordinal > 0 && ordinal < 4000 => {
+            let numerals = [(1000, "m"), (900, "cm"), (500, "d"), (400, "cd"), (100, "c"), (90, "xc"),
+                            (50, "l"), (40, "xl"), (10, "x"), (9, "ix"), (5, "v"), (4, "iv"), (1, "i")];
+            let mut roman = String::new();
+            let mut rest = ordinal;
+            for (value, numeral) in numerals.iter() {
+                while rest >= *value {
+                    roman.push_str(numeral);
+                    rest -= value;
+                }
+            }
+            roman
+        }
+        _ => return ordinal.to_string()
+    };
+
+    return match kind {
+        Some("A") | Some("I") => formatted.to_uppercase(),
+        _ => formatted
+    };
+}
+
+/// Handler for `<input type="checkbox">` inside list items, which become GFM task list items
+#[derive(Default)]
+pub struct CheckboxHandler;
+
+impl TagHandler for CheckboxHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        if printer.options.flavor != Flavor::Gfm || get_tag_attr(tag, "type").map(|kind| kind.to_lowercase()) != Some(String::from("checkbox")) {
+            return;
+        }
+
+        // only a checkbox leading the list item is a task marker
+        let in_list_item = printer.parent_chain.last().is_some_and(|parent| parent == "li" || parent == "p")
+            && printer.parent_chain.iter().any(|parent| parent == "li");
+        let last_line = printer.data.trim_end().rsplit('\n').next().unwrap_or_default().trim_start();
+        let at_item_start = last_line == "*"
+            || last_line.strip_suffix('.').is_some_and(|order| !order.is_empty() && order.chars().all(|ch| ch.is_ascii_digit()));
+        if !in_list_item || !at_item_start {
+            return;
+        }
+
+        match get_tag_attr(tag, "checked") {
+            Some(_) => printer.append_str("[x] "),
+            None => printer.append_str("[ ] "),
+        }
+    }
+
+    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {
+
+    }
+}
@@ -0,0 +1,234 @@
+use super::TagHandler;
+use super::StructuredPrinter;
+use super::MathMode;
+
+use crate::common::{get_tag_attr, tag_name, text_content};
+
+use markup5ever_rcdom::{Handle,NodeData};
+
+/// Handler for formulas: MathML `math` elements, MathJax `<script type="math/tex">` and the
+/// HTML rendering of KaTeX, which duplicates its MathML and is skipped.
+///
+/// LaTeX comes from the `application/x-tex` annotation when there's one, otherwise it's
+/// converted from presentation MathML, as is linear text
+#[derive(Default)]
+pub struct MathHandler;
+
+impl TagHandler for MathHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        let name = tag_name(tag);
+        let (tex, block) = match name.as_ref() {
+            "math" => (annotation(tag), get_tag_attr(tag, "display").as_deref() == Some("block")),
+            "script" => (Some(text_content(tag, &[], false)), get_tag_attr(tag, "type").is_some_and(|kind| kind.contains("mode=display"))),
+            // rendering of a formula
+            _ => return
+        };
+
+        let formula = match &printer.options.math {
+            MathMode::Latex => tex.unwrap_or_else(|| to_latex(tag)),
+            // TeX scripts have no MathML to convert
+            MathMode::Text if name == "script" => tex.unwrap_or_default(),
+            MathMode::Text => to_text(tag),
+            MathMode::Placeholder(token) => token.clone()
+        };
+        let formula = formula.trim();
+        if formula.is_empty() {
+            return;
+        }
+
+        let formula = match printer.options.math {
+            MathMode::Latex if block => format!("$${}$$", formula),
+            MathMode::Latex => format!("${}$", formula),
+            _ => formula.to_string()
+        };
+        if block {
+            // display formulas are paragraphs of their own
+            printer.insert_newline();
+            printer.insert_newline();
+            printer.append_str(&formula);
+            printer.insert_newline();
+            printer.insert_newline();
+        } else {
+            printer.append_str(&formula);
+        }
+    }
+
+    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {
+
+    }
+
+    fn skip_descendants(&self) -> bool {
+        return true;
+    }
+}
+
+/// Whether `tag` is a MathJax TeX script
+pub fn is_tex_script(tag: &Handle) -> bool {
+    return get_tag_attr(tag, "type").is_some_and(|kind| kind.starts_with("math/tex"));
+}
+
+/// Whether `tag` is the HTML rendering of a KaTeX formula, hidden from screen readers
+/// because its MathML copy is next to it
+pub fn is_math_rendering(tag: &Handle) -> bool {
+    return get_tag_attr(tag, "class").is_some_and(|class| class.split_whitespace().any(|class| class == "katex-html"));
+}
+
+/// Text of the TeX annotation of a MathML formula
+fn annotation(tag: &Handle) -> Option<String> {
+    for child in tag.children.borrow().iter() {
+        let name = tag_name(child);
+        if name == "annotation" {
+            let encoding = get_tag_attr(child, "encoding").unwrap_or_default();
+            if encoding == "application/x-tex" || encoding == "TeX" {
+                return Some(text_content(child, &[], false));
+            }
+        } else if name == "semantics" || name == "mrow" {
+            if let Some(tex) = annotation(child) {
+                return Some(tex);
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Identifiers LaTeX has commands for
+const FUNCTIONS: [&str; 12] = ["sin", "cos", "tan", "cot", "log", "ln", "exp", "lim", "max", "min", "det", "gcd"];
+
+/// Converts presentation MathML to LaTeX
+fn to_latex(tag: &Handle) -> String {
+    let children = element_children(tag);
+    let child = |index: usize| children.get(index).map(to_latex).unwrap_or_default();
+    let name = tag_name(tag);
+    return match name.as_ref() {
+        "mi" if FUNCTIONS.contains(&text_content(tag, &[], false).as_str()) => format!("\\{} ", text_content(tag, &[], false)),
+        "mi" | "mn" | "mo" => text_content(tag, &[], false).chars().map(latex_symbol).collect(),
+        "mtext" | "ms" => format!("\\text{{{}}}", text_content(tag, &[], false)),
+        "mspace" => String::from("\\ "),
+        "msup" => format!("{}^{{{}}}", child(0), child(1)),
+        "msub" => format!("{}_{{{}}}", child(0), child(1)),
+        "msubsup" => format!("{}_{{{}}}^{{{}}}", child(0), child(1), child(2)),
+        "mfrac" => format!("\\frac{{{}}}{{{}}}", child(0), child(1)),
+        "msqrt" => format!("\\sqrt{{{}}}", joined(&children, to_latex, "")),
+        "mroot" => format!("\\sqrt[{}]{{{}}}", child(1), child(0)),
+        "mover" => match text_content(children.get(1).unwrap_or(tag), &[], false).as_ref() {
+            "^" | "ˆ" => format!("\\hat{{{}}}", child(0)),
+            "¯" | "‾" | "_" => format!("\\overline{{{}}}", child(0)),
+            "→" | "⃗" => format!("\\vec{{{}}}", child(0)),
+            "~" | "˜" => format!("\\tilde{{{}}}", child(0)),
+            "˙" | "." => format!("\\dot{{{}}}", child(0)),
+            _ => format!("\\overset{{{}}}{{{}}}", child(1), child(0))
+        },
+        "munder" => format!("\\underset{{{}}}{{{}}}", child(1), child(0)),
+        // limits of sums, products and integrals
+        "munderover" => format!("{}_{{{}}}^{{{}}}", child(0), child(1), child(2)),
+        "mfenced" => {
+            let (open, close, separator) = fences(tag);
+            format!("\\left{}{}\\right{}", latex_fence(&open), joined(&children, to_latex, &separator), latex_fence(&close))
+        }
+        "mtable" => format!("\\begin{{matrix}}{}\\end{{matrix}}", joined(&children, to_latex, " \\\\ ")),
+        "mtr" | "mlabeledtr" => joined(&children, to_latex, " & "),
+        "annotation" | "annotation-xml" | "mphantom" => String::new(),
+        // only the presentation of a formula is rendered
+        "semantics" => child(0),
+        _ => joined(&children, to_latex, "")
+    };
+}
+
+/// Converts presentation MathML to linear text: `x^2`, `(a+b)/2`
+fn to_text(tag: &Handle) -> String {
+    let children = element_children(tag);
+    let child = |index: usize| children.get(index).map(|child| group(&to_text(child))).unwrap_or_default();
+    let name = tag_name(tag);
+    return match name.as_ref() {
+        "mo" => {
+            let operator = text_content(tag, &[], false);
+            match operator.as_ref() {
+                "(" | ")" | "[" | "]" | "{" | "}" | "|" | "" | "\u{2061}" | "\u{2062}" => operator,
+                "," | ";" => format!("{} ", operator),
+                _ => format!(" {} ", operator)
+            }
+        }
+        "mi" if FUNCTIONS.contains(&text_content(tag, &[], false).as_str()) => format!("{} ", text_content(tag, &[], false)),
+        "mi" | "mn" | "mtext" | "ms" => text_content(tag, &[], false),
+        "mspace" => String::from(" "),
+        "msup" => format!("{}^{}", child(0), child(1)),
+        "msub" => format!("{}_{}", child(0), child(1)),
+        "msubsup" => format!("{}_{}^{}", child(0), child(1), child(2)),
+        "mfrac" => format!("{}/{}", child(0), child(1)),
+        "msqrt" => format!("√{}", group(&joined(&children, to_text, ""))),
+        "mroot" => format!("{}^(1/{})", child(0), to_text(children.get(1).unwrap_or(tag))),
+        "munderover" => format!("{}_{}^{}", child(0), child(1), child(2)),
+        "mfenced" => {
+            let (open, close, separator) = fences(tag);
+            format!("{}{}{}", open, joined(&children, to_text, &format!("{} ", separator.trim())), close)
+        }
+        "mtable" => format!("[{}]", joined(&children, to_text, "; ")),
+        "mtr" | "mlabeledtr" => joined(&children, to_text, ", "),
+        "annotation" | "annotation-xml" | "mphantom" => String::new(),
+        "semantics" => children.first().map(to_text).unwrap_or_default(),
+        _ => {
+            let text = joined(&children, to_text, "");
+            // collapse spaces of nested operators
+            if name == "math" { text.split_whitespace().collect::<Vec<&str>>().join(" ") } else { text }
+        }
+    };
+}
+
+/// Puts linear text in parentheses unless it's a single number or identifier
+fn group(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= 1 || text.chars().all(char::is_alphabetic) || text.chars().all(|ch| ch.is_ascii_digit() || ch == '.') {
+        return text.to_string();
+    }
+    return format!("({})", text);
+}
+
+/// Opening, closing and separator characters of `mfenced`
+fn fences(tag: &Handle) -> (String, String, String) {
+    let open = get_tag_attr(tag, "open").unwrap_or_else(|| String::from("("));
+    let close = get_tag_attr(tag, "close").unwrap_or_else(|| String::from(")"));
+    let separator = get_tag_attr(tag, "separators").and_then(|separators| separators.trim().chars().next()).unwrap_or(',');
+    return (open, close, format!("{} ", separator));
+}
+
+fn latex_fence(fence: &str) -> String {
+    return match fence {
+        "" => String::from("."),
+        "{" | "}" => format!("\\{}", fence),
+        _ => fence.to_string()
+    };
+}
+
+/// LaTeX command of common symbols, other characters are kept
+fn latex_symbol(ch: char) -> String {
+    let command = match ch {
+        'α' => "\\alpha ", 'β' => "\\beta ", 'γ' => "\\gamma ", 'δ' => "\\delta ", 'ε' | 'ϵ' => "\\epsilon ", 'θ' => "\\theta ",
+        'λ' => "\\lambda ", 'μ' => "\\mu ", 'π' => "\\pi ", 'ρ' => "\\rho ", 'σ' => "\\sigma ", 'τ' => "\\tau ", 'φ' | 'ϕ' => "\\phi ",
+        'ω' => "\\omega ", 'Γ' => "\\Gamma ", 'Δ' => "\\Delta ", 'Θ' => "\\Theta ", 'Λ' => "\\Lambda ", 'Π' => "\\Pi ", 'Σ' => "\\Sigma ",
+        'Φ' => "\\Phi ", 'Ω' => "\\Omega ",
+        '∑' => "\\sum ", '∏' => "\\prod ", '∫' => "\\int ", '∞' => "\\infty ", '∂' => "\\partial ", '∇' => "\\nabla ",
+        '×' => "\\times ", '⋅' | '·' => "\\cdot ", '±' => "\\pm ", '∓' => "\\mp ", '−' => "-", '÷' => "\\div ",
+        '≤' => "\\leq ", '≥' => "\\geq ", '≠' => "\\neq ", '≈' => "\\approx ", '≡' => "\\equiv ", '∝' => "\\propto ",
+        '→' => "\\to ", '⇒' => "\\Rightarrow ", '⇔' => "\\Leftrightarrow ", '∈' => "\\in ", '∉' => "\\notin ", '⊂' => "\\subset ",
+        '∪' => "\\cup ", '∩' => "\\cap ", '∀' => "\\forall ", '∃' => "\\exists ", '…' => "\\ldots ", '⋯' => "\\cdots ",
+        '{' => "\\{", '}' => "\\}", '%' => "\\%", '#' => "\\#", '&' => "\\&",
+        // invisible function application and times
+        '\u{2061}' | '\u{2062}' => "",
+        _ => return ch.to_string()
+    };
+    return command.to_string();
+}
+
+/// Converts every element and joins the results with `separator`
+fn joined(children: &[Handle], convert: fn(&Handle) -> String, separator: &str) -> String {
+    return children.iter().map(convert).collect::<Vec<String>>().join(separator);
+}
+
+fn element_children(tag: &Handle) -> Vec<Handle> {
+    return tag.children.borrow().iter()
+        .filter(|child| matches!(child.data, NodeData::Element { .. }))
+        .cloned()
+        .collect();
+}
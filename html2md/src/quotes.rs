@@ -31,4 +31,22 @@ impl TagHandler for QuoteHandler {
         printer.insert_newline();
         printer.insert_newline();
     }
-}
\ No newline at end of file
+}
+
+/// Handler for inline quotations, they're put in quotation marks. Nested ones use single marks
+#[derive(Default)]
+pub struct InlineQuoteHandler {
+    nested: bool
+}
+
+impl TagHandler for InlineQuoteHandler {
+
+    fn handle(&mut self, _tag: &Handle, printer: &mut StructuredPrinter) {
+        self.nested = printer.parent_chain.iter().filter(|tag| *tag == "q").count() % 2 == 1;
+        printer.append_str(if self.nested { "‘" } else { "“" });
+    }
+
+    fn after_handle(&mut self, printer: &mut StructuredPrinter) {
+        printer.append_str(if self.nested { "’" } else { "”" });
+    }
+}
@@ -0,0 +1,82 @@
+use super::TagHandler;
+use super::StructuredPrinter;
+use super::RubyMode;
+use super::escape_markdown;
+
+use std::collections::VecDeque;
+
+use crate::common::text_content;
+
+use markup5ever_rcdom::{Handle,NodeData};
+
+/// Handler for ruby annotations, such as furigana. Base texts are paired with their `rt` annotations
+/// and printed as the ruby mode says, `rp` fallback parentheses are always dropped
+#[derive(Default)]
+pub struct RubyHandler;
+
+impl TagHandler for RubyHandler {
+
+    fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
+        let mut text = String::new();
+        for (base, reading) in segments(tag) {
+            match (&printer.options.ruby, reading) {
+                (RubyMode::Parenthesized, Some(reading)) if !base.is_empty() => text.push_str(&format!("{}({})", base, reading)),
+                (RubyMode::Parenthesized, Some(reading)) | (RubyMode::Reading, Some(reading)) => text.push_str(&reading),
+                _ => text.push_str(&base)
+            }
+        }
+
+        let text = escape_markdown(printer, &text);
+        printer.append_str(&text);
+    }
+
+    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {
+
+    }
+
+    fn skip_descendants(&self) -> bool {
+        return true;
+    }
+}
+
+/// Base texts of the ruby with their annotations. `rb` elements and text runs are bases,
+/// each `rt` or `rtc` annotates the first base not annotated yet
+fn segments(ruby: &Handle) -> Vec<(String, Option<String>)> {
+    let mut segments = vec![];
+    let mut bases: VecDeque<String> = VecDeque::new();
+    // whether the last base is a text run that following text continues
+    let mut open_run = false;
+
+    for child in ruby.children.borrow().iter() {
+        let name = match child.data {
+            NodeData::Element { ref name, .. } => name.local.to_string(),
+            _ => String::new()
+        };
+        match name.as_ref() {
+            "rp" => {}
+            "rt" | "rtc" => {
+                let reading = text_content(child, &["rp"], true);
+                segments.push((bases.pop_front().unwrap_or_default(), Some(reading)));
+                open_run = false;
+            }
+            "rb" => {
+                bases.push_back(text_content(child, &["rp"], true));
+                open_run = false;
+            }
+            _ => {
+                let text = text_content(child, &["rp"], true);
+                if text.trim().is_empty() {
+                    continue;
+                }
+                match bases.back_mut() {
+                    Some(run) if open_run => run.push_str(&text),
+                    _ => bases.push_back(text)
+                }
+                open_run = true;
+            }
+        }
+    }
+
+    segments.extend(bases.into_iter().map(|base| (base, None)));
+    return segments;
+}
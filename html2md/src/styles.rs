@@ -26,24 +26,56 @@ impl TagHandler for StyleHandler {
     }
 
     fn after_handle(&mut self, printer: &mut StructuredPrinter) {
-        return;
-
-        let non_space_offset = printer.data[self.start_pos..].find(|ch: char| !ch.is_whitespace());
-        if non_space_offset.is_none() {
-            // only spaces or no text at all
-            return;
-        }
-
-        let first_non_space_pos = self.start_pos + non_space_offset.unwrap();
-        let last_non_space_pos = printer.data.trim_end_matches(|ch: char| ch.is_whitespace()).len();
-        
         // finishing markup
         match self.style_type.as_ref() {
-            "b" | "strong" => apply_at_bounds(printer, first_non_space_pos, last_non_space_pos, "**"),
-            "i" | "em" => apply_at_bounds(printer, first_non_space_pos, last_non_space_pos, "*"),
-            "s" | "del" => apply_at_bounds(printer, first_non_space_pos, last_non_space_pos, "~~"),
-            "u" | "ins" => apply_at_bounds(printer, first_non_space_pos, last_non_space_pos, "__"),
+            "b" | "strong" => emphasize(printer, self.start_pos, "**"),
+            "i" | "em" | "cite" => emphasize(printer, self.start_pos, "*"),
+            "s" | "strike" | "del" => emphasize(printer, self.start_pos, "~~"),
+            // no Markdown syntax for these
+            "u" | "ins" | "mark" | "small" if printer.options.inline_html => {
+                let (start, end) = (format!("<{}>", self.style_type), format!("</{}>", self.style_type));
+                wrap_at_bounds(printer, self.start_pos, &start, &end);
+            }
             _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+/// Puts `start` and `end` around text printed since `start_pos`, leaving surrounding whitespace outside
+fn wrap_at_bounds(printer: &mut StructuredPrinter, start_pos: usize, start: &str, end: &str) {
+    if let Some(non_space_offset) = printer.data[start_pos..].find(|ch: char| !ch.is_whitespace()) {
+        let last_non_space_pos = printer.data.trim_end_matches(|ch: char| ch.is_whitespace()).len();
+        printer.data.insert_str(last_non_space_pos, end);
+        printer.data.insert_str(start_pos + non_space_offset, start);
+    }
+}
+
+/// Wraps text printed since `start_pos` in `mark`, leaving surrounding whitespace outside.
+/// Does nothing if emphasis is disabled or there's no text
+pub(crate) fn emphasize(printer: &mut StructuredPrinter, start_pos: usize, mark: &str) {
+    if !printer.options.emphasis {
+        return;
+    }
+
+    let non_space_offset = printer.data[start_pos..].find(|ch: char| !ch.is_whitespace());
+    if non_space_offset.is_none() {
+        // only spaces or no text at all
+        return;
+    }
+
+    let first_non_space_pos = start_pos + non_space_offset.unwrap();
+    let last_non_space_pos = printer.data.trim_end_matches(|ch: char| ch.is_whitespace()).len();
+    apply_at_bounds(printer, first_non_space_pos, last_non_space_pos, mark);
+}
+
+/// Makes text printed since `start_pos` strong as a whole, strong parts inside it are merged
+pub(crate) fn strengthen(printer: &mut StructuredPrinter, start_pos: usize) {
+    if !printer.options.emphasis {
+        return;
+    }
+
+    // nested strong marks would end the outer ones
+    let text = printer.data.split_off(start_pos).replace("**", "");
+    printer.append_str(&text);
+    emphasize(printer, start_pos, "**");
+}
@@ -1,123 +1,342 @@
 use super::{walk, clean_markdown};
-use super::TagHandler;
+use super::{TagHandler, TagHandlerFactory};
 use super::StructuredPrinter;
+use super::{Flavor, TableMode};
 
-use std::{collections::HashMap, cmp};
+use crate::common::{get_tag_attr, tag_name};
 
-use markup5ever_rcdom::{Handle,NodeData};
+use std::{collections::HashMap, cmp, rc::Rc};
 
-#[derive(Default)]
-pub struct TableHandler;
+use markup5ever_rcdom::Handle;
 
-impl TagHandler for TableHandler {
+/// Spans above these are clamped, like browsers do
+const MAX_COLSPAN: usize = 1000;
+const MAX_ROWSPAN: usize = 65534;
+
+/// Handler for tables. Cells and nested tables are converted with the custom handlers of the document
+pub struct TableHandler<'c> {
+    custom: &'c HashMap<String, Box<dyn TagHandlerFactory>>
+}
+
+impl<'c> TableHandler<'c> {
+
+    /// Handler converting cells with `custom` tag handlers, as `walk` does
+    pub fn new(custom: &'c HashMap<String, Box<dyn TagHandlerFactory>>) -> Self {
+        TableHandler { custom }
+    }
+}
+
+impl TagHandler for TableHandler<'_> {
 
     fn handle(&mut self, tag: &Handle, printer: &mut StructuredPrinter) {
-        let mut table_markup = String::new();
-        let any_matcher = |cell: &Handle| { let name = tag_name(cell); name == "td" || name == "th" };
-
-        // detect cell width, counts
-        let column_count : usize;
-        let mut column_widths : Vec<usize>;
-        let rows = find_children(tag, "tr");
-        {
-            // detect row count
-            let most_big_row = rows.iter().max_by(|left, right| collect_children(&left, any_matcher).len().cmp(&collect_children(&right, any_matcher).len()));
-            if most_big_row.is_none() {
-                // we don't have rows with content at all
-                return;
+        if printer.parent_chain.iter().any(|tag| tag == "td" || tag == "th") {
+            // nested tables can't be represented inside cells, they're rendered after the outer table
+            return;
+        }
+
+        let grid = TableGrid::new(tag);
+        if grid.columns == 0 {
+            // we don't have rows with content at all
+            return;
+        }
+
+        let options = printer.options.clone();
+        printer.insert_newline();
+        printer.insert_newline();
+        match options.tables {
+            TableMode::Pipe => render_pipe(&grid, printer, self.custom),
+            TableMode::Linear => render_linear(&grid, printer, self.custom)
+        }
+
+        // nested tables can't be represented inside cells, render them after this one
+        for nested in &grid.nested {
+            walk(nested, printer, self.custom);
+        }
+    }
+
+    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {
+
+    }
+
+    fn skip_descendants(&self) -> bool {
+        return true;
+    }
+}
+
+/// Slot of a table grid
+#[derive(Debug, Clone)]
+pub struct GridCell {
+    /// The `td` or `th` element covering the slot
+    pub handle: Handle,
+    /// Whether this is the top left slot of the cell, other slots are covered by its spans
+    pub origin: bool,
+}
+
+/// HTML table laid out on a rectangular grid, with `colspan` and `rowspan` expanded.
+///
+/// Rows of `tfoot` come last whatever their position in the HTML, as browsers render them.
+/// Rowspans don't cross row groups.
+#[derive(Debug, Clone)]
+pub struct TableGrid {
+    /// The `caption` element
+    pub caption: Option<Handle>,
+    /// Rows of `thead`, or the leading rows with `th` cells only when there's no `thead`
+    pub head: Vec<Vec<Option<GridCell>>>,
+    /// Rows of `tbody`, rows outside of row groups and rows of `tfoot`
+    pub body: Vec<Vec<Option<GridCell>>>,
+    /// Width of every row, slots without a cell are `None`
+    pub columns: usize,
+    /// Tables inside cells, they're not part of the grid
+    pub nested: Vec<Handle>,
+}
+
+impl TableGrid {
+
+    /// Lays out the rows of `table`, nested tables excluded
+    pub fn new(table: &Handle) -> TableGrid {
+        let mut caption = None;
+        let mut head: Vec<Vec<Option<GridCell>>> = vec![];
+        let mut body: Vec<Vec<Option<GridCell>>> = vec![];
+        let mut foot: Vec<Vec<Option<GridCell>>> = vec![];
+        let mut loose_rows: Vec<Handle> = vec![];
+
+        for child in table.children.borrow().iter() {
+            let name = tag_name(child);
+            if name == "tr" {
+                loose_rows.push(child.clone());
+                continue;
             }
-            // have rows with content, set column count
-            column_count = collect_children(&most_big_row.unwrap(), any_matcher).len();
-            column_widths = vec![3; column_count];
-
-            // detect max column width
-            for row in &rows {
-                let cells = collect_children(row, any_matcher);
-                for index in 0..column_count {
-                    // from regular rows
-                    if let Some(cell) = cells.get(index) {
-                        let text = to_text(cell);
-                        column_widths[index] = cmp::max(column_widths[index], text.chars().count());
-                    }
+            if !loose_rows.is_empty() {
+                body.append(&mut layout(&loose_rows));
+                loose_rows.clear();
+            }
+
+            let rows = collect_children(child, |row| tag_name(row) == "tr");
+            match name.as_ref() {
+                "caption" if caption.is_none() => caption = Some(child.clone()),
+                "thead" => head.append(&mut layout(&rows)),
+                "tbody" => body.append(&mut layout(&rows)),
+                "tfoot" => foot.append(&mut layout(&rows)),
+                _ => {}
+            }
+        }
+        body.append(&mut layout(&loose_rows));
+        body.append(&mut foot);
+
+        if head.is_empty() {
+            // header cells without `thead`
+            let leading = body.iter()
+                .take_while(|row| row.iter().any(Option::is_some) && row.iter().flatten().all(|cell| tag_name(&cell.handle) == "th"))
+                .count();
+            head = body.drain(..leading).collect();
+        }
+
+        let columns = head.iter().chain(body.iter()).map(Vec::len).max().unwrap_or(0);
+        for row in head.iter_mut().chain(body.iter_mut()) {
+            row.resize(columns, None);
+        }
+
+        let mut nested = vec![];
+        for cell in head.iter().chain(body.iter()).flatten().flatten().filter(|cell| cell.origin) {
+            find_tables(&cell.handle, &mut nested);
+        }
+
+        TableGrid { caption, head, body, columns, nested }
+    }
+}
+
+/// Texts of the row slots, spanned slots are left empty unless `spanned` is set
+fn texts(row: &[Option<GridCell>], spanned: bool, printer: &mut StructuredPrinter, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) -> Vec<String> {
+    row.iter()
+        .map(|slot| match slot {
+            Some(cell) if cell.origin || spanned => to_text(&cell.handle, printer, custom),
+            _ => String::new()
+        })
+        .collect()
+}
+
+/// Header text of every column, texts of several header rows are joined. `None` if the table has no header
+fn header_texts(grid: &TableGrid, printer: &mut StructuredPrinter, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) -> Option<Vec<String>> {
+    if grid.head.is_empty() {
+        return None;
+    }
+
+    // header cells apply to all their columns
+    let head: Vec<Vec<String>> = grid.head.iter().map(|row| texts(row, true, printer, custom)).collect();
+    let header = (0..grid.columns)
+        .map(|index| {
+            let mut parts: Vec<&str> = vec![];
+            for row in &head {
+                if !row[index].is_empty() && !parts.contains(&row[index].as_str()) {
+                    parts.push(&row[index]);
                 }
             }
+            parts.join(" ")
+        })
+        .collect();
+    return Some(header);
+}
+
+/// Renders the table as a pipe table. It has exactly one header row: several ones are merged,
+/// the first row is used if there's none
+fn render_pipe(grid: &TableGrid, printer: &mut StructuredPrinter, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) {
+    // cells are converted in reading order, so are references to their links
+    let caption = grid.caption.as_ref().filter(|_| printer.options.captions).map(|caption| to_text(caption, printer, custom));
+    let header = header_texts(grid, printer, custom);
+    // spanned slots of the body are left empty
+    let mut body: Vec<Vec<String>> = grid.body.iter().map(|row| texts(row, false, printer, custom)).collect();
+    let header = header.unwrap_or_else(|| body.remove(0));
+    let header_cells = grid.head.last().or(grid.body.first()).unwrap();
+
+    // detect max column width
+    let mut column_widths = vec![3; grid.columns];
+    for row in std::iter::once(&header).chain(body.iter()) {
+        for (index, text) in row.iter().enumerate() {
+            column_widths[index] = cmp::max(column_widths[index], text.chars().count());
+        }
+    }
+
+    let mut table_markup = String::new();
+    push_row(&mut table_markup, &header, &column_widths);
+
+    // add header-body divider row
+    table_markup.push('|');
+    for index in 0..grid.columns {
+        let width = column_widths[index];
+
+        // try to detect alignment from the header
+        let alignment = header_cells[index].as_ref().and_then(|cell| get_tag_attr(&cell.handle, "align")).unwrap_or_default();
+
+        // push lines according to alignment, fallback to default behaviour
+        match alignment.as_ref() {
+            "left" => { table_markup.push(':'); table_markup.push_str(&"-".repeat(width - 1)); }
+            "center" => { table_markup.push(':'); table_markup.push_str(&"-".repeat(width - 2)); table_markup.push(':'); }
+            "right" => { table_markup.push_str(&"-".repeat(width - 1)); table_markup.push(':'); }
+            _ => table_markup.push_str(&"-".repeat(width))
         }
+        table_markup.push('|');
+    }
+    table_markup.push('\n');
 
-        // header row must always be present
-        for (idx, row) in rows.iter().enumerate() {
-            table_markup.push('|');
-            let cells = collect_children(row, any_matcher);
-            for index in 0..column_count { // we need to fill all cells in a column, even if some rows don't have enough
-                let padded_cell_text = pad_cell_text(&cells.get(index), column_widths[index]);
-                table_markup.push_str(&padded_cell_text);
-                table_markup.push('|');
+    for row in &body {
+        push_row(&mut table_markup, row, &column_widths);
+    }
+
+    if let Some(caption) = caption.filter(|caption| !caption.is_empty()) {
+        // markdown has no captions, put it in its own paragraph before the table
+        table_markup.insert_str(0, &format!("{}\n\n", caption));
+    }
+    printer.append_str(&table_markup);
+}
+
+/// Renders the table as a paragraph of sentences: the caption, then a sentence per row
+fn render_linear(grid: &TableGrid, printer: &mut StructuredPrinter, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) {
+    let mut sentences: Vec<String> = vec![];
+    if let Some(caption) = grid.caption.as_ref().filter(|_| printer.options.captions) {
+        sentences.push(to_text(caption, printer, custom));
+    }
+    // without body rows, the header rows are the content
+    let (rows, header) = if grid.body.is_empty() {
+        (&grid.head, None)
+    } else {
+        (&grid.body, header_texts(grid, printer, custom))
+    };
+
+    for row in rows {
+        // spanned cells are repeated, so each sentence holds the whole row
+        let cells = texts(row, true, printer, custom);
+        let mut sentence = String::new();
+        for (index, text) in cells.iter().enumerate() {
+            // skip empty cells and the slots of a colspan
+            if text.is_empty() || (index > 0 && matches!((&row[index], &row[index - 1]), (Some(a), Some(b)) if Rc::ptr_eq(&a.handle, &b.handle))) {
+                continue;
             }
-            table_markup.push('\n');
-
-            if idx == 0 {
-                // first row is a header row
-                // add header-body divider row
-                table_markup.push('|');
-                for index in 0..column_count {
-                    let width = column_widths[index];
-                    if width < 3 {
-                        // no point in aligning, just post as-is
-                        table_markup.push_str(&"-".repeat(width));
-                        table_markup.push('|');
-                        continue;
-                    }
+            if !sentence.is_empty() {
+                // a cell ending with a colon labels the next one
+                sentence.push_str(if sentence.ends_with(':') { " " } else { "; " });
+            }
+            match header.as_ref().map(|header| header[index].as_str()) {
+                Some(label) if !label.is_empty() && label != text => sentence.push_str(&format!("{}: {}", label, text)),
+                _ => sentence.push_str(text)
+            }
+        }
+        sentences.push(sentence);
+    }
 
-                    // try to detect alignment
-                    let mut alignment = String::new();
-                    if let Some(header_cell) = cells.get(index) {
-                        // we have a header, try to extract alignment from it
-                        alignment = match header_cell.data {
-                            NodeData::Element { ref attrs, .. } => {
-                                let attrs = attrs.borrow();
-                                let align_attr = attrs.iter().find(|attr| attr.name.local.to_string() == "align");
-                                align_attr.map(|attr| attr.value.to_string()).unwrap_or_default()
-                            }
-                            _ => String::new()
-                        };
-                    }
+    let paragraph = sentences.iter()
+        .map(|sentence| sentence.trim())
+        .filter(|sentence| !sentence.is_empty())
+        .map(|sentence| if sentence.ends_with(|ch| ch == '.' || ch == '!' || ch == '?') { sentence.to_string() } else { format!("{}.", sentence) })
+        .collect::<Vec<String>>()
+        .join(" ");
+    printer.append_str(&paragraph);
+}
+
+/// Places the cells of `rows`, a row group, on a grid
+fn layout(rows: &[Handle]) -> Vec<Vec<Option<GridCell>>> {
+    let mut grid: Vec<Vec<Option<GridCell>>> = vec![vec![]; rows.len()];
+    for (row_index, row) in rows.iter().enumerate() {
+        let mut column = 0;
+        for cell in collect_children(row, |cell| { let name = tag_name(cell); name == "td" || name == "th" }) {
+            // skip slots taken by rowspans from the rows above
+            while matches!(grid[row_index].get(column), Some(Some(_))) {
+                column += 1;
+            }
 
-                    // push lines according to alignment, fallback to default behaviour
-                    match alignment.as_ref() {
-                        "left" => { table_markup.push(':'); table_markup.push_str(&"-".repeat(width - 1)); }
-                        "center" => { table_markup.push(':'); table_markup.push_str(&"-".repeat(width - 2)); table_markup.push(':'); }
-                        "right" => { table_markup.push_str(&"-".repeat(width - 1)); table_markup.push(':'); }
-                        _ => table_markup.push_str(&"-".repeat(width))
+            let colspan = span(&cell, "colspan").unwrap_or(1).clamp(1, MAX_COLSPAN);
+            let remaining_rows = rows.len() - row_index;
+            // `rowspan="0"` spans the rest of the row group
+            let rowspan = match span(&cell, "rowspan") {
+                Some(0) => remaining_rows,
+                Some(rowspan) => rowspan.clamp(1, MAX_ROWSPAN).min(remaining_rows),
+                None => 1
+            };
+
+            for row_offset in 0..rowspan {
+                let slots = &mut grid[row_index + row_offset];
+                if slots.len() < column + colspan {
+                    slots.resize(column + colspan, None);
+                }
+                for slot in &mut slots[column..column + colspan] {
+                    // overlapping cells: the first one wins
+                    if slot.is_none() {
+                        *slot = Some(GridCell { handle: cell.clone(), origin: false });
                     }
-                    table_markup.push('|');
                 }
-                table_markup.push('\n');
             }
+            if let Some(Some(origin)) = grid[row_index].get_mut(column) {
+                origin.origin = true;
+            }
+            column += colspan;
         }
-
-        printer.insert_newline();
-        printer.insert_newline();
-        printer.append_str(&table_markup);
     }
 
-    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {
+    return grid;
+}
 
-    }
+/// Numeric value of a span attribute, `None` if missing or invalid
+fn span(cell: &Handle, attr_name: &str) -> Option<usize> {
+    get_tag_attr(cell, attr_name).and_then(|value| value.trim().parse().ok())
+}
 
-    fn skip_descendants(&self) -> bool {
-        return true;
+/// Appends a table row, filling missing cells with spaces
+fn push_row(table_markup: &mut String, row: &[String], column_widths: &[usize]) {
+    table_markup.push('|');
+    for (index, width) in column_widths.iter().enumerate() {
+        table_markup.push_str(&pad_cell_text(row.get(index).map(String::as_str), *width));
+        table_markup.push('|');
     }
+    table_markup.push('\n');
 }
 
 /// Pads cell text from right and left so it looks centered inside the table cell
 /// ### Arguments
-/// `tag` - optional reference to currently processed handle, text is extracted from here
+/// `text` - optional text of the cell
 ///
 /// `column_width` - precomputed column width to compute padding length from
-fn pad_cell_text(tag: &Option<&Handle>, column_width: usize) -> String {
+fn pad_cell_text(text: Option<&str>, column_width: usize) -> String {
     let mut result = String::new();
-    if let Some(cell) = tag {
-        // have header at specified position
-        let text = to_text(cell);
+    if let Some(text) = text {
         // compute difference between width and text length
         let len_diff = column_width - text.chars().count();
         if len_diff > 0 {
@@ -127,16 +346,16 @@ fn pad_cell_text(tag: &Option<&Handle>, column_width: usize) -> String {
                 let pad_len = len_diff / 2;
                 let remainder = len_diff % 2;
                 result.push_str(&" ".repeat(pad_len));
-                result.push_str(&text);
+                result.push_str(text);
                 result.push_str(&" ".repeat(pad_len + remainder));
             } else {
                 // it's just one space, add at the end
-                result.push_str(&text);
+                result.push_str(text);
                 result.push(' ');
             }
         } else {
             // shouldn't pad, text fills whole cell
-            result.push_str(&text);
+            result.push_str(text);
         }
     } else {
         // no text in this cell, fill cell with spaces
@@ -147,30 +366,15 @@ fn pad_cell_text(tag: &Option<&Handle>, column_width: usize) -> String {
     return result;
 }
 
-/// Extracts tag name from passed tag
-/// Returns empty string if it's not an html element
-fn tag_name(tag: &Handle) -> String {
-    return match tag.data {
-        NodeData::Element { ref name, .. } => name.local.to_string(),
-        _ => String::new()
-    }
-}
-
-/// Find descendants of this tag with tag name `name`
-/// This includes both direct children and descendants
-fn  find_children(tag: &Handle, name: &str) -> Vec<Handle> {
-    let mut result: Vec<Handle> = vec![];
-    let children = tag.children.borrow();
-    for child in children.iter() {
-        if tag_name(&child) == name {
+/// Find outermost tables among descendants of this tag, tables inside them are not included
+fn find_tables(tag: &Handle, result: &mut Vec<Handle>) {
+    for child in tag.children.borrow().iter() {
+        if tag_name(child) == "table" {
             result.push(child.clone());
+        } else {
+            find_tables(child, result);
         }
-
-        let mut descendants = find_children(&child, name);
-        result.append(&mut descendants);
     }
-
-    return result;
 }
 
 /// Collect direct children that satisfy the predicate
@@ -191,11 +395,25 @@ where P: Fn(&Handle) -> bool {
 
 /// Convert html tag to text. This collects all tag children in correct order where they're observed
 /// and concatenates their text, recursively.
-fn  to_text(tag: &Handle) -> String {
-    let mut printer = StructuredPrinter::default();
-    walk(tag, &mut printer, &HashMap::default());
+fn  to_text(tag: &Handle, printer: &mut StructuredPrinter, custom: &HashMap<String, Box<dyn TagHandlerFactory>>) -> String {
+    let options = printer.options.clone();
+    let mut cell_printer = StructuredPrinter::default();
+    cell_printer.options = options.clone();
+    // links of cells are numbered along with the others of the document
+    cell_printer.references = std::mem::take(&mut printer.references);
+    walk(tag, &mut cell_printer, custom);
+    printer.references = cell_printer.references;
 
+    let result = clean_markdown(&cell_printer.data);
+    if options.tables == TableMode::Linear {
+        // cells are parts of a sentence
+        return result.split_whitespace().collect::<Vec<&str>>().join(" ");
+    }
 
-    let result = clean_markdown(&printer.data);
-    return result.replace("\n", "<br/>");
-}
\ No newline at end of file
+    let result = result.replace("\n", "<br/>");
+    if options.flavor == Flavor::Gfm {
+        // pipes would end the cell
+        return result.replace('|', "\\|");
+    }
+    return result;
+}
@@ -0,0 +1,42 @@
+extern crate html2md;
+
+use html2md::{parse_html_with_options, ConversionOptions};
+use pretty_assertions::assert_eq;
+
+fn references(options: ConversionOptions) -> ConversionOptions {
+    ConversionOptions { references: true, ..options }
+}
+
+#[test]
+fn test_references_plain() {
+    let html = r#"<p>See <a href="https://example.com/a">the docs</a> and <a href="https://example.com/b">the FAQ</a>.</p>
+        <p>Back to <a href="https://example.com/a">the docs</a>, <a name="anchor">no link</a>.</p>"#;
+    let text = parse_html_with_options(html, &references(ConversionOptions::plain_text()));
+    assert_eq!(text, "See the docs [1] and the FAQ [2].\n\nBack to the docs [1], no link.\n\n\
+        References\n\n1. https://example.com/a\n2. https://example.com/b")
+}
+
+#[test]
+fn test_references_markdown() {
+    let html = r#"<p><a href="https://example.com"><strong>Example</strong></a> and <a href="https://example.com">https://example.com</a></p>"#;
+    let md = parse_html_with_options(html, &references(ConversionOptions::gfm()));
+    assert_eq!(md, "**Example** [1] and https://example.com [1]\n\nReferences\n\n1. https://example.com")
+}
+
+#[test]
+fn test_references_off() {
+    let md = parse_html_with_options(r#"<a href="https://example.com">Example</a>"#, &ConversionOptions::markdown());
+    assert_eq!(md, "[Example](https://example.com)")
+}
+
+#[test]
+fn test_references_in_tables() {
+    let html = r#"<p><a href="https://a.com">A</a></p><table><caption><a href="https://c.com">C</a></caption><tr><th>H</th></tr>
+        <tr><td><a href="https://b.com">B</a> and <a href="https://a.com">A</a></td></tr></table>"#;
+    let md = parse_html_with_options(html, &references(ConversionOptions::markdown()));
+    assert_eq!(md, "A [1]\n\nC [2]\n\n|       H       |\n|---------------|\n|B [3] and A [1]|\n\n\
+        References\n\n1. https://a.com\n2. https://c.com\n3. https://b.com");
+    let text = parse_html_with_options(html, &references(ConversionOptions::plain_text()));
+    assert_eq!(text, "A [1]\n\nC [2]. H: B [3] and A [1].\n\n\
+        References\n\n1. https://a.com\n2. https://c.com\n3. https://b.com")
+}
@@ -0,0 +1,25 @@
+//! Conversion helpers running every test against both presets
+#![allow(dead_code)]
+
+use html2md::{parse_html_with_options, ConversionOptions};
+use pretty_assertions::assert_eq;
+
+/// Converts `html` with the full Markdown preset, checking the plain text preset gives the same result
+pub fn parse_html(html: &str) -> String {
+    let markdown = parse_html_with_options(html, &ConversionOptions::markdown());
+    assert_eq!(markdown, parse_html_with_options(html, &ConversionOptions::plain_text()), "plain text preset differs");
+    markdown
+}
+
+/// Converts `html` with the full Markdown preset only, for constructs the plain text preset renders differently
+pub fn parse_markdown(html: &str) -> String {
+    parse_html_with_options(html, &ConversionOptions::markdown())
+}
+
+/// Converts `html` with the full Markdown and the plain text presets, in that order
+pub fn parse_html_presets(html: &str) -> (String, String) {
+    (
+        parse_html_with_options(html, &ConversionOptions::markdown()),
+        parse_html_with_options(html, &ConversionOptions::plain_text()),
+    )
+}
@@ -0,0 +1,44 @@
+extern crate html2md;
+
+mod common;
+
+use common::parse_html_presets;
+use html2md::{parse_html_with_options, ConversionOptions};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+const PAGE: &str = indoc! {r#"
+    <nav><a href="/">Home</a> <a href="/news">News</a></nav>
+    <main>
+        <article>Article text<address>Written by Jane</address></article>
+        <aside>Related stories</aside>
+        <form><fieldset><legend>Subscribe</legend>Weekly digest</fieldset></form>
+    </main>
+    <footer>Copyright</footer>
+    "#};
+
+#[test]
+fn test_block_elements() {
+    let (md, plain) = parse_html_presets(PAGE);
+    assert_eq!(plain, md.replace("[Home](/) [News](/news)", "Home News"));
+    assert_eq!(md, "\
+[Home](/) [News](/news)
+
+Article text
+
+Written by Jane
+
+Related stories
+
+Subscribe
+
+Weekly digest
+
+Copyright");
+}
+
+#[test]
+fn test_boilerplate_dropped() {
+    let options = ConversionOptions { drop_boilerplate: true, ..ConversionOptions::plain_text() };
+    assert_eq!(parse_html_with_options(PAGE, &options), "Article text\n\nWritten by Jane");
+}
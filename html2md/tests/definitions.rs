@@ -0,0 +1,62 @@
+extern crate html2md;
+
+mod common;
+
+use common::parse_html_presets;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_definition_list() {
+    let (md, plain) = parse_html_presets(indoc! {"
+    <p>Glossary</p>
+    <dl>
+        <dt>API</dt>
+        <dd>Application programming interface</dd>
+        <dt>CLI</dt>
+        <dt>Command line</dt>
+        <dd>Text interface of a program.</dd>
+        <dd>See also <i>shell</i>.</dd>
+    </dl>
+    <p>End</p>
+    "});
+
+    assert_eq!(md, "\
+Glossary
+
+**API**
+
+Application programming interface
+
+**CLI**
+
+**Command line**
+
+Text interface of a program.
+
+See also *shell*.
+
+End");
+    assert_eq!(plain, "\
+Glossary
+
+API
+
+Application programming interface
+
+CLI
+
+Command line
+
+Text interface of a program.
+
+See also shell.
+
+End");
+}
+
+#[test]
+fn test_definition_list_inline() {
+    let (md, _) = parse_html_presets("<dl><dt>Term</dt><dd>Definition with a <a href=\"https://example.com\">link</a></dd></dl>");
+    assert_eq!(md, "**Term**\n\nDefinition with a [link](https://example.com)");
+}
@@ -0,0 +1,132 @@
+extern crate html2md;
+
+mod common;
+
+use std::collections::HashMap;
+
+use html2md::{parse_html_custom, ConversionOptions, EmbedMode, Handle};
+use html2md::common::get_tag_attr;
+use html2md::embeds::{Embed, EmbedProvider, EmbedRegistry};
+use common::{parse_markdown, parse_html_presets};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_youtube_simple() {
+    let (md, plain) = parse_html_presets("<iframe src='https://www.youtube.com/embed/zE-dmXZp3nU?wmode=opaque' class='fr-draggable' width='640' height='360'></iframe>");
+    assert_eq!(md, "[![Embedded YouTube video](https://img.youtube.com/vi/zE-dmXZp3nU/0.jpg)](https://www.youtube.com/watch?v=zE-dmXZp3nU)");
+    assert_eq!(plain, "")
+}
+
+#[test]
+fn test_instagram_simple() {
+    let (md, plain) = parse_html_presets("<iframe src='https://www.instagram.com/p/B1BKr9Wo8YX/embed/' width='600' height='600'></iframe>");
+    assert_eq!(md, "[![Embedded Instagram post](https://www.instagram.com/p/B1BKr9Wo8YX/media/?size=m)](https://www.instagram.com/p/B1BKr9Wo8YX/embed/)");
+    assert_eq!(plain, "")
+}
+
+#[test]
+fn test_vkontakte_simple() {
+    let (md, plain) = parse_html_presets("<iframe src='https://vk.com/video_ext.php?oid=-76477496&id=456239454&hash=ebfdc2d386617b97' width='640' height='360' frameborder='0' allowfullscreen></iframe>");
+    assert_eq!(md, "[![Embedded VK video](https://st.vk.com/images/icons/video_empty_2x.png)](https://vk.com/video-76477496_456239454)");
+    assert_eq!(plain, "")
+}
+
+#[test]
+fn test_vimeo_simple() {
+    let (md, plain) = parse_html_presets("<iframe src='https://player.vimeo.com/video/76979871?h=8272103f6e' title='The New Vimeo Player'></iframe>");
+    assert_eq!(md, "[Embedded Vimeo video](https://vimeo.com/76979871)");
+    assert_eq!(plain, "")
+}
+
+#[test]
+fn test_twitter_blockquote() {
+    let html = r#"<p>Before</p><blockquote class="twitter-tweet"><p lang="en" dir="ltr">just setting up my twttr</p>&mdash; jack (@jack) <a href="https://twitter.com/jack/status/20?ref_src=twsrc%5Etfw">March 21, 2006</a></blockquote><script async src="https://platform.twitter.com/widgets.js"></script><p>After</p>"#;
+    let (md, plain) = parse_html_presets(html);
+    assert_eq!(md, "Before\n\n[Embedded Twitter post](https://twitter.com/i/status/20)\n\nAfter");
+    assert_eq!(plain, "Before\n\nAfter")
+}
+
+#[test]
+fn test_instagram_blockquote() {
+    let md = parse_html_custom("<blockquote class='instagram-media' data-instgrm-permalink='https://www.instagram.com/p/B1BKr9Wo8YX/?utm_source=ig_embed'><a href='https://www.instagram.com/p/B1BKr9Wo8YX/'>View this post on Instagram</a></blockquote>", &HashMap::new(), &placeholders());
+    assert_eq!(md, "[Post: Instagram]")
+}
+
+#[test]
+fn test_twitter_blockquote_foreign_link() {
+    let (md, plain) = parse_html_presets("<blockquote class='twitter-tweet'>See <a href='https://example.com/?next=https://twitter.com/jack/status/20'>this</a></blockquote>");
+    assert_eq!(md, "> See [this](https://example.com/?next=https://twitter.com/jack/status/20)");
+    assert_eq!(plain, "> See this")
+}
+
+#[test]
+fn test_plain_blockquote() {
+    let (md, plain) = parse_html_presets("<blockquote class='twitter-tweet'>Not a tweet</blockquote>");
+    assert_eq!(md, "> Not a tweet");
+    assert_eq!(plain, "> Not a tweet")
+}
+
+#[test]
+fn test_spotify_soundcloud_codepen() {
+    let html = r#"<iframe src="https://open.spotify.com/embed/track/4uLU6hMCjMI75M1A2tKUQC?utm_source=generator"></iframe>
+        <iframe src="https://w.soundcloud.com/player/?url=https%3A//api.soundcloud.com/tracks/293&amp;auto_play=false" title="Flickermood"></iframe>
+        <iframe src="https://codepen.io/team/codepen/embed/preview/PNaGbb?default-tab=result" title="CodePen Embed - Pen Example"></iframe>"#;
+    let md = parse_markdown(html);
+    assert_eq!(md, "[Embedded Spotify track](https://open.spotify.com/track/4uLU6hMCjMI75M1A2tKUQC)\n\n\
+        [Embedded SoundCloud track](https://api.soundcloud.com/tracks/293)\n\n\
+        [Embedded CodePen pen](https://codepen.io/team/codepen/pen/PNaGbb)");
+    let text = parse_html_custom(html, &HashMap::new(), &placeholders());
+    assert_eq!(text, "[Track: Spotify]\n\n[Track: Flickermood]\n\n[Pen: CodePen Embed - Pen Example]")
+}
+
+#[test]
+fn test_placeholder_title() {
+    let text = parse_html_custom("<iframe src='https://www.youtube.com/embed/dQw4w9WgXcQ' title='Never Gonna Give You Up'></iframe>", &HashMap::new(), &placeholders());
+    assert_eq!(text, "[Video: Never Gonna Give You Up]")
+}
+
+#[test]
+fn test_unknown_iframe() {
+    let (md, plain) = parse_html_presets("<p>Map</p><iframe src='https://maps.example.com/embed'></iframe>");
+    assert_eq!(md, "Map\n\n<iframe src=\"https://maps.example.com/embed\"></iframe>");
+    assert_eq!(plain, "Map")
+}
+
+#[test]
+fn test_custom_provider() {
+    let html = "<iframe src='https://www.dailymotion.com/embed/video/x7tgad0'></iframe><iframe src='https://www.youtube.com/embed/dQw4w9WgXcQ'></iframe>";
+    let mut registry = EmbedRegistry::empty();
+    registry.register(Dailymotion);
+    let mut custom = HashMap::new();
+    registry.install(&mut custom);
+    let md = parse_html_custom(html, &custom, &ConversionOptions::markdown());
+    assert_eq!(md, "[Embedded Dailymotion video](https://www.dailymotion.com/video/x7tgad0)\n\n<iframe src=\"https://www.youtube.com/embed/dQw4w9WgXcQ\"></iframe>");
+    let text = parse_html_custom(html, &custom, &placeholders());
+    assert_eq!(text, "[Video: Dailymotion clip]")
+}
+
+#[test]
+fn test_custom_provider_in_tables() {
+    let mut registry = EmbedRegistry::empty();
+    registry.register(Dailymotion);
+    let mut custom = HashMap::new();
+    registry.install(&mut custom);
+    let html = "<table><tr><th>Clip</th></tr><tr><td><iframe src='https://www.dailymotion.com/embed/video/x7tgad0'></iframe>\
+        <table><tr><td><iframe src='https://www.dailymotion.com/embed/video/x8abc12'></iframe></td></tr></table></td></tr></table>";
+    let text = parse_html_custom(html, &custom, &placeholders());
+    assert_eq!(text, "Clip: [Video: Dailymotion clip].\n\n[Video: Dailymotion clip].")
+}
+
+struct Dailymotion;
+
+impl EmbedProvider for Dailymotion {
+    fn detect(&self, tag: &Handle) -> Option<Embed> {
+        let src = get_tag_attr(tag, "src")?;
+        let id = src.strip_prefix("https://www.dailymotion.com/embed/video/")?;
+        return Some(Embed { title: Some(String::from("Dailymotion clip")), ..Embed::new("Dailymotion", "video", format!("https://www.dailymotion.com/video/{}", id)) });
+    }
+}
+
+fn placeholders() -> ConversionOptions {
+    return ConversionOptions { embeds: EmbedMode::Placeholder, ..ConversionOptions::plain_text() };
+}
@@ -0,0 +1,47 @@
+extern crate html2md;
+
+mod common;
+
+use common::parse_html_presets;
+use html2md::{parse_html_with_options, ConversionOptions};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+const FIGURE: &str = indoc! {r#"
+    <p>Before the figure.</p>
+    <figure>
+        <img src="https://example.com/chart.png" alt="Chart">
+        <figcaption>Figure 1: <b>sales</b> by region</figcaption>
+    </figure>
+    <p>After the figure.</p>
+    "#};
+
+#[test]
+fn test_figure() {
+    let (md, plain) = parse_html_presets(FIGURE);
+    assert_eq!(md, "\
+Before the figure.
+
+![Chart](https://example.com/chart.png)
+
+Figure 1: **sales** by region
+
+After the figure.");
+    assert_eq!(plain, "\
+Before the figure.
+
+Figure 1: sales by region
+
+After the figure.");
+}
+
+#[test]
+fn test_captions_disabled() {
+    let options = ConversionOptions { captions: false, ..ConversionOptions::plain_text() };
+    assert_eq!(parse_html_with_options(FIGURE, &options), "Before the figure.\n\nAfter the figure.");
+
+    let table = "<table><caption>Prices</caption><tr><th>Item</th></tr><tr><td>Tea</td></tr></table>";
+    assert_eq!(parse_html_with_options(table, &options), "Item: Tea.");
+    let options = ConversionOptions { captions: false, ..ConversionOptions::markdown() };
+    assert_eq!(parse_html_with_options(table, &options), "|Item|\n|----|\n|Tea |");
+}
@@ -0,0 +1,63 @@
+extern crate html2md;
+
+use html2md::{parse_html_with_options, ConversionOptions};
+use pretty_assertions::assert_eq;
+use pulldown_cmark::{html, Options, Parser};
+
+/// Converts `html` to GFM and renders it back to HTML with a GFM parser
+fn round_trip(html: &str) -> (String, String) {
+    let markdown = parse_html_with_options(html, &ConversionOptions::gfm());
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new_ext(&markdown, options));
+    (markdown, rendered.replace('\n', ""))
+}
+
+#[test]
+fn test_gfm_headings() {
+    let (md, rendered) = round_trip("<h1>Title</h1><p>Text</p><h2>Section</h2><h4>- Deep</h4>");
+    assert_eq!(md, "# Title\n\nText\n\n## Section\n\n#### - Deep");
+    assert_eq!(rendered, "<h1>Title</h1><p>Text</p><h2>Section</h2><h4>- Deep</h4>");
+}
+
+#[test]
+fn test_gfm_fenced_code() {
+    let (md, rendered) = round_trip(r#"<pre><code class="hljs language-rust">fn main() {}</code></pre><pre>a ``` b</pre>"#);
+    assert_eq!(md, "```rust\nfn main() {}\n```\n\n````\na ``` b\n````");
+    assert_eq!(rendered, r#"<pre><code class="language-rust">fn main() {}</code></pre><pre><code>a ``` b</code></pre>"#);
+}
+
+#[test]
+fn test_gfm_strikethrough() {
+    let (md, rendered) = round_trip("<p>Price: <del>10</del> <strike>9</strike> <s>8</s> 7</p>");
+    assert_eq!(md, "Price: ~~10~~ ~~9~~ ~~8~~ 7");
+    assert_eq!(rendered, "<p>Price: <del>10</del> <del>9</del> <del>8</del> 7</p>");
+}
+
+#[test]
+fn test_gfm_task_list() {
+    let (md, rendered) = round_trip(r#"<ul><li><input type="checkbox" checked> Done</li><li><input type="checkbox"> Todo</li><li>Plain <input type="checkbox"></li></ul>"#);
+    assert_eq!(md, "* [x] Done\n* [ ] Todo\n* Plain");
+    assert_eq!(rendered, r#"<ul><li><input disabled="" type="checkbox" checked=""/>Done</li><li><input disabled="" type="checkbox"/>Todo</li><li>Plain</li></ul>"#);
+}
+
+#[test]
+fn test_gfm_autolinks() {
+    let (md, rendered) = round_trip(r#"<p><a href="https://example.com/a_b">https://example.com/a_b</a>, <a href="mailto:me@example.com">me@example.com</a> and <a href="https://example.com">a site</a></p>"#);
+    assert_eq!(md, "<https://example.com/a_b>, <me@example.com> and [a site](https://example.com)");
+    assert_eq!(rendered, r#"<p><a href="https://example.com/a_b">https://example.com/a_b</a>, <a href="mailto:me@example.com">me@example.com</a> and <a href="https://example.com">a site</a></p>"#);
+}
+
+#[test]
+fn test_gfm_tables() {
+    let (md, rendered) = round_trip("<table><tr><th>Operator</th><th>Meaning</th></tr><tr><td>a | b</td><td><b>or</b></td></tr></table>");
+    assert_eq!(md, "|Operator|Meaning|\n|--------|-------|\n| a \\| b |**or** |");
+    assert_eq!(rendered, "<table><thead><tr><th>Operator</th><th>Meaning</th></tr></thead><tbody><tr><td>a | b</td><td><strong>or</strong></td></tr></tbody></table>");
+}
+
+#[test]
+fn test_gfm_presets_unchanged() {
+    // the original flavor keeps setext headers and bare fences
+    let md = parse_html_with_options(r#"<h1>Title</h1><pre class="language-rust">x</pre>"#, &ConversionOptions::markdown());
+    assert_eq!(md, "Title\n==========\n\n```\nx\n```");
+}
@@ -1,48 +1,91 @@
 extern crate html2md;
 
-use html2md::parse_html;
+mod common;
+
+use common::{parse_html_presets, parse_markdown};
 use pretty_assertions::assert_eq;
 
 #[test]
 fn test_image_native_simple() {
-    let md = parse_html("<img src=\"https://i.redd.it/vesfbmwfkz811.png\" alt=\"image of Linus holding his laptop\" title=\"Daddy Linus\" />");
-    assert_eq!(md, "![image of Linus holding his laptop](https://i.redd.it/vesfbmwfkz811.png \"Daddy Linus\")")
+    let (md, plain) = parse_html_presets("<img src=\"https://i.redd.it/vesfbmwfkz811.png\" alt=\"image of Linus holding his laptop\" title=\"Daddy Linus\" />");
+    assert_eq!(md, "![image of Linus holding his laptop](https://i.redd.it/vesfbmwfkz811.png \"Daddy Linus\")");
+    assert_eq!(plain, "")
 }
 
 #[test]
 fn test_image_native_without_title() {
-    let md = parse_html("<img src=\"https://i.redd.it/l0ne52x7fh611.png\" alt=\"image of usual kill -9 sequence\" />");
-    assert_eq!(md, "![image of usual kill -9 sequence](https://i.redd.it/l0ne52x7fh611.png)")
+    let (md, plain) = parse_html_presets("<img src=\"https://i.redd.it/l0ne52x7fh611.png\" alt=\"image of usual kill -9 sequence\" />");
+    assert_eq!(md, "![image of usual kill -9 sequence](https://i.redd.it/l0ne52x7fh611.png)");
+    assert_eq!(plain, "")
 }
 
 #[test]
 fn test_image_embedded_html() {
-    let md = parse_html("<img src=\"https://i.redd.it/un4h28uwtp711.png\" alt=\"comics about Mac and GNU/Linux\" title=\"Look at me, brother\" height=\"150\" width=\"150\" />");
-    assert_eq!(md, "<img alt=\"comics about Mac and GNU/Linux\" src=\"https://i.redd.it/un4h28uwtp711.png\" title=\"Look at me, brother\" height=\"150\" width=\"150\" />")
+    let (md, plain) = parse_html_presets("<img src=\"https://i.redd.it/un4h28uwtp711.png\" alt=\"comics about Mac and GNU/Linux\" title=\"Look at me, brother\" height=\"150\" width=\"150\" />");
+    assert_eq!(md, "<img alt=\"comics about Mac and GNU/Linux\" src=\"https://i.redd.it/un4h28uwtp711.png\" title=\"Look at me, brother\" height=\"150\" width=\"150\" />");
+    assert_eq!(plain, "")
 }
 
 #[test]
 fn test_image_embedded_with_unsupported_html() {
     // srcset is unsupported in Markdown
-    let md = parse_html("<img src=\"https://i.redd.it/07onlc10x5711.png\" alt=\"HACKERMAN\" title=\"When you reboot instead of exiting vim\" height=\"150\" width=\"150\" srcset=\"image1 image2\" align=\"center\" />");
-    assert_eq!(md, "<img alt=\"HACKERMAN\" src=\"https://i.redd.it/07onlc10x5711.png\" title=\"When you reboot instead of exiting vim\" height=\"150\" width=\"150\" align=\"center\" />")
+    let (md, plain) = parse_html_presets("<img src=\"https://i.redd.it/07onlc10x5711.png\" alt=\"HACKERMAN\" title=\"When you reboot instead of exiting vim\" height=\"150\" width=\"150\" srcset=\"image1 image2\" align=\"center\" />");
+    assert_eq!(md, "<img alt=\"HACKERMAN\" src=\"https://i.redd.it/07onlc10x5711.png\" title=\"When you reboot instead of exiting vim\" height=\"150\" width=\"150\" align=\"center\" />");
+    assert_eq!(plain, "")
 }
 
 #[test]
 fn test_image_src_issue() {
-    let md = parse_html("<img src=\"https://dybr.ru/img/43/1532265494_android-Kanedias\" width=\"auto\" height=\"500\" >");
-    assert_eq!(md, "<img src=\"https://dybr.ru/img/43/1532265494_android-Kanedias\" height=\"500\" width=\"auto\" />")
+    let (md, plain) = parse_html_presets("<img src=\"https://dybr.ru/img/43/1532265494_android-Kanedias\" width=\"auto\" height=\"500\" >");
+    assert_eq!(md, "<img src=\"https://dybr.ru/img/43/1532265494_android-Kanedias\" height=\"500\" width=\"auto\" />");
+    assert_eq!(plain, "")
 }
 
 #[test]
 fn test_image_with_space_issue() {
-    let md = parse_html("<img src=\"https://i.redd.it/l0ne 52x7f h611.png\" alt=\"image of usual kill -9 sequence\" />");
-    assert_eq!(md, "![image of usual kill -9 sequence](https://i.redd.it/l0ne%2052x7f%20h611.png)")
+    let (md, plain) = parse_html_presets("<img src=\"https://i.redd.it/l0ne 52x7f h611.png\" alt=\"image of usual kill -9 sequence\" />");
+    assert_eq!(md, "![image of usual kill -9 sequence](https://i.redd.it/l0ne%2052x7f%20h611.png)");
+    assert_eq!(plain, "")
 }
 
 
 #[test]
 fn test_image_with_query_issue() {
-    let md = parse_html("<img src=\"https://instagram.ftll1-1.fna.fbcdn.net/vp/4c753762a3cd58ec2cd55f7e20f87e5c/5D39A8B3/t51.2885-15/sh0.08/e35/p640x640/54511922_267736260775264_8482507773977053160_n.jpg?_nc_ht=instagram.ftll1-1.fna.fbcdn.net\" style=\"width: 494px;\">");
-    assert_eq!(md, "![](https://instagram.ftll1-1.fna.fbcdn.net/vp/4c753762a3cd58ec2cd55f7e20f87e5c/5D39A8B3/t51.2885-15/sh0.08/e35/p640x640/54511922_267736260775264_8482507773977053160_n.jpg?_nc_ht=instagram.ftll1-1.fna.fbcdn.net)")
+    let (md, plain) = parse_html_presets("<img src=\"https://instagram.ftll1-1.fna.fbcdn.net/vp/4c753762a3cd58ec2cd55f7e20f87e5c/5D39A8B3/t51.2885-15/sh0.08/e35/p640x640/54511922_267736260775264_8482507773977053160_n.jpg?_nc_ht=instagram.ftll1-1.fna.fbcdn.net\" style=\"width: 494px;\">");
+    assert_eq!(md, "![](https://instagram.ftll1-1.fna.fbcdn.net/vp/4c753762a3cd58ec2cd55f7e20f87e5c/5D39A8B3/t51.2885-15/sh0.08/e35/p640x640/54511922_267736260775264_8482507773977053160_n.jpg?_nc_ht=instagram.ftll1-1.fna.fbcdn.net)");
+    assert_eq!(plain, "")
+}
+
+#[test]
+fn test_image_lazy_loading() {
+    let md = parse_markdown("<img src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw=\" data-src=\"https://example.com/photo.jpg\" alt=\"Photo\">");
+    assert_eq!(md, "![Photo](https://example.com/photo.jpg)")
+}
+
+#[test]
+fn test_image_srcset() {
+    let md = parse_markdown("<img src=\"small.jpg\" srcset=\"small.jpg 480w, https://res.example.com/w_800,h_600/medium.jpg 800w, large.jpg 1600w\" alt=\"Sunset\">");
+    assert_eq!(md, "![Sunset](large.jpg)");
+    let md = parse_markdown("<img src=\"small.jpg\" srcset=\"small.jpg 480w, https://res.example.com/w_800,h_600/medium.jpg 800w, large.jpg 1600w\" sizes=\"(max-width: 600px) 100vw, 400px\" alt=\"Sunset\">");
+    assert_eq!(md, "![Sunset](https://res.example.com/w_800,h_600/medium.jpg)");
+    let md = parse_markdown("<img src=\"logo.png\" srcset=\"logo.png, logo@2x.png 2x\" alt=\"Logo\">");
+    assert_eq!(md, "![Logo](logo@2x.png)")
+}
+
+#[test]
+fn test_image_picture() {
+    let md = parse_markdown("<picture><source type=\"image/webp\" srcset=\"photo.webp 1200w\"><source srcset=\"photo.jpg 1200w, photo-small.jpg 600w\"><img src=\"photo-small.jpg\" alt=\"Photo\"></picture>");
+    assert_eq!(md, "![Photo](photo.webp)")
+}
+
+#[test]
+fn test_image_block_style() {
+    let md = parse_markdown("Before<img src=\"photo.jpg\" style=\"display: block\">After");
+    assert_eq!(md, "Before\n\n![](photo.jpg)\n\nAfter")
+}
+
+#[test]
+fn test_image_spacers() {
+    let md = parse_markdown("<p>Text<img src=\"https://example.com/spacer.gif\"><img src=\"https://tracker.example.com/open?id=42\" width=\"1\" height=\"1\"></p>");
+    assert_eq!(md, "Text")
 }
@@ -0,0 +1,32 @@
+extern crate html2md;
+
+mod common;
+
+use common::parse_html_presets;
+use html2md::{parse_html_with_options, ConversionOptions};
+use pretty_assertions::assert_eq;
+
+const HTML: &str = r#"<p>The <abbr title="World Health
+    Organization">WHO</abbr> met on <time datetime="2024-05-01T10:00Z">May 1st</time>, press <kbd>Ctrl+*</kbd> to <mark>highlight</mark>,
+    <u>underline</u> or <small>shrink</small> <ins>text</ins>.</p>"#;
+
+#[test]
+fn test_inline_semantics() {
+    let (md, plain) = parse_html_presets(HTML);
+    assert_eq!(md, "The WHO met on May 1st, press `Ctrl+*` to highlight, underline or shrink text.");
+    assert_eq!(plain, md);
+}
+
+#[test]
+fn test_inline_semantics_enabled() {
+    let options = ConversionOptions { abbreviations: true, datetimes: true, inline_html: true, ..ConversionOptions::markdown() };
+    assert_eq!(parse_html_with_options(HTML, &options), "\
+The WHO (World Health Organization) met on 2024-05-01T10:00Z, press `Ctrl+*` to <mark>highlight</mark>, \
+<u>underline</u> or <small>shrink</small> <ins>text</ins>.");
+}
+
+#[test]
+fn test_abbreviation_without_title() {
+    let options = ConversionOptions { abbreviations: true, ..ConversionOptions::plain_text() };
+    assert_eq!(parse_html_with_options("<abbr>NASA</abbr> and <abbr title='NATO'>NATO</abbr> launch", &options), "NASA and NATO launch");
+}
@@ -1,7 +1,9 @@
 extern crate html2md;
 extern crate spectral;
 
-use html2md::parse_html;
+mod common;
+
+use common::{parse_html, parse_html_presets};
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -60,8 +62,10 @@ fn test_strong_inside_link() {
     let mut html = String::new();
     let mut html_file = File::open("test-samples/dybr-bug-with-strong-inside-link.html").unwrap();
     html_file.read_to_string(&mut html).expect("File must be readable");
-    let result = parse_html(&html);
-    assert_that(&result).contains("[**Just God**](http://fanfics.me/ficXXXXXXX)");
+    let (md, plain) = parse_html_presets(&html);
+    assert_that(&md).contains("[**Just God**](http://fanfics.me/ficXXXXXXX)");
+    assert_that(&plain).contains("Just God");
+    assert_that(&plain.contains("fanfics.me")).is_false();
 }
 
 #[test]
@@ -69,15 +73,17 @@ fn test_tables_with_newlines() {
     let mut html = String::new();
     let mut html_file = File::open("test-samples/dybr-bug-with-tables-masked.html").unwrap();
     html_file.read_to_string(&mut html).expect("File must be readable");
-    let result = parse_html(&html);
+    let (md, plain) = parse_html_presets(&html);
 
-    // all lines starting with | should end with | as well
-    let invalid_table_lines: Vec<&str> = result.lines()
-        .filter(|line| line.starts_with("|"))
-        .filter(|line| !line.ends_with("|"))
-        .collect();
+    for result in [md, plain].iter() {
+        // all lines starting with | should end with | as well
+        let invalid_table_lines: Vec<&str> = result.lines()
+            .filter(|line| line.starts_with("|"))
+            .filter(|line| !line.ends_with("|"))
+            .collect();
 
-    assert_that(&invalid_table_lines).is_empty();
+        assert_that(&invalid_table_lines).is_empty();
+    }
 }
 
 #[test]
@@ -85,13 +91,14 @@ fn test_tables_crash2() {
     let mut html = String::new();
     let mut html_file = File::open("test-samples/dybr-bug-with-tables-2-masked.html").unwrap();
     html_file.read_to_string(&mut html).expect("File must be readable");
-    let table_with_vertical_header = parse_html(&html);
+    let (md, plain) = parse_html_presets(&html);
 
-    assert_that!(table_with_vertical_header).contains(indoc! {"
+    assert_that!(md).contains(indoc! {"
         |Current Conditions:|Open all year. No reservations. No services.|
         |-------------------|--------------------------------------------|
         |   Reservations:   |              No reservations.              |
         |       Fees        |                  No fee.                   |
         |      Water:       |                 No water.                  |"
     });
+    assert_that!(plain).contains("Current Conditions: Open all year. No reservations. No services. Reservations: No reservations. Fees; No fee. Water: No water.");
 }
\ No newline at end of file
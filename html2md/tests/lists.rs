@@ -1,6 +1,8 @@
 extern crate html2md;
 
-use html2md::parse_html;
+mod common;
+
+use common::{parse_html, parse_html_presets};
 use pretty_assertions::assert_eq;
 
 
@@ -234,3 +236,118 @@ Phrases to describe me:
 * Can count to five
 * Learning to count to six B)")
 }
+
+#[test]
+fn test_list_ordered_start_and_value() {
+    let md = parse_html(r#"
+        <ol start="5">
+            <li>Fifth</li>
+            <li value="9">Ninth</li>
+            <li>Tenth<p>with a paragraph</p></li>
+        </ol>
+    "#);
+    assert_eq!(md, "\
+5. Fifth
+9. Ninth
+10. Tenth
+
+    with a paragraph")
+}
+
+#[test]
+fn test_list_ordered_reversed() {
+    let md = parse_html(r#"
+        <ol reversed>
+            <li>Three</li>
+            <!-- comments and whitespace don't count -->
+            <li>Two</li>
+            <li>One</li>
+        </ol>
+        <ol reversed start="10"><li>Ten</li><li value="3">Three</li><li>Two</li></ol>
+    "#);
+    assert_eq!(md, "\
+3. Three
+2. Two
+1. One
+
+10. Ten
+3. Three
+2. Two")
+}
+
+#[test]
+fn test_list_ordered_out_of_range() {
+    // only 32-bit integers are valid, as in browsers
+    let (md, plain) = parse_html_presets(r#"
+        <ol start="9223372036854775807"><li>a</li><li>b</li></ol>
+        <ol reversed start="-9223372036854775808"><li>a</li><li>b</li></ol>
+        <ol start="2147483647"><li>a</li><li value="-2147483648">b</li><li>c</li></ol>
+    "#);
+    assert_eq!(md, "\
+1. a
+2. b
+
+2. a
+1. b
+
+1. a
+2. b
+3. c");
+    assert_eq!(plain, "\
+1. a
+2. b
+
+2. a
+1. b
+
+2147483647. a
+-2147483648. b
+-2147483647. c")
+}
+
+#[test]
+fn test_list_ordered_negative() {
+    // `-1.` would be a bullet in Markdown
+    let (md, plain) = parse_html_presets(r#"<ol start="-1"><li>a</li><li>b</li><li>c</li></ol><ol reversed start="1"><li>x</li><li>y</li></ol>"#);
+    assert_eq!(md, "1. a\n2. b\n3. c\n\n1. x\n0. y");
+    assert_eq!(plain, "-1. a\n0. b\n1. c\n\n1. x\n0. y")
+}
+
+#[test]
+fn test_list_ordered_types() {
+    let (md, plain) = parse_html_presets(r#"
+        <ol type="a" start="26"><li>Z</li><li>AA</li></ol>
+        <ol type="I" start="3"><li>Three</li><li type="1">Four</li><li>Five</li></ol>
+        <ol type="i" start="0"><li>Zero</li></ol>
+    "#);
+    assert_eq!(md, "\
+26. Z
+27. AA
+
+3. Three
+4. Four
+5. Five
+
+0. Zero");
+    assert_eq!(plain, "\
+z. Z
+aa. AA
+
+III. Three
+4. Four
+V. Five
+
+0. Zero")
+}
+
+#[test]
+fn test_list_nested_indentation() {
+    let md = parse_html(r#"
+        <menu>
+            <li>Menu<ul><li>Nested</li></ul></li>
+        </menu>
+    "#);
+    assert_eq!(md, "\
+* Menu
+  * Nested")
+}
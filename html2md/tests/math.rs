@@ -0,0 +1,43 @@
+extern crate html2md;
+
+mod common;
+
+use common::parse_html_presets;
+use html2md::{parse_html_with_options, ConversionOptions, MathMode};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_math_annotation() {
+    // KaTeX output: MathML with the TeX source, then its HTML rendering
+    let (md, plain) = parse_html_presets(r#"<p>Energy is <span class="katex"><span class="katex-mathml"><math><semantics>
+        <mrow><mi>E</mi><mo>=</mo><mi>m</mi><msup><mi>c</mi><mn>2</mn></msup></mrow>
+        <annotation encoding="application/x-tex">E = mc^2</annotation></semantics></math></span>
+        <span class="katex-html" aria-hidden="true"><span class="mord">E</span><span class="mrel">=</span></span></span> here.</p>"#);
+    assert_eq!(md, "Energy is $E = mc^2$ here.");
+    assert_eq!(plain, "Energy is E = mc^2 here.");
+}
+
+#[test]
+fn test_math_converted() {
+    let (md, plain) = parse_html_presets(r#"<p>Roots are</p><math display="block"><mi>x</mi><mo>=</mo><mfrac>
+        <mrow><mo>−</mo><mi>b</mi><mo>±</mo><msqrt><msup><mi>b</mi><mn>2</mn></msup><mo>−</mo><mn>4</mn><mi>a</mi><mi>c</mi></msqrt></mrow>
+        <mrow><mn>2</mn><mi>a</mi></mrow></mfrac></math><p>as usual, and <math><mi>sin</mi><mi>α</mi></math> too.</p>"#);
+    assert_eq!(md, "\
+Roots are
+
+$$x=\\frac{-b\\pm \\sqrt{b^{2}-4ac}}{2a}$$
+
+as usual, and $\\sin \\alpha$ too.");
+    assert_eq!(plain, "Roots are\n\nx = (− b ± √(b^2 − 4ac))/(2a)\n\nas usual, and sin α too.");
+}
+
+#[test]
+fn test_math_script_and_placeholder() {
+    let html = r#"<p>Area <script type="math/tex">\pi r^2</script> of a circle.</p><script type="math/tex; mode=display">\int_0^1 x\,dx</script>"#;
+    let (md, plain) = parse_html_presets(html);
+    assert_eq!(md, "Area $\\pi r^2$ of a circle.\n\n$$\\int_0^1 x\\,dx$$");
+    assert_eq!(plain, "Area \\pi r^2 of a circle.\n\n\\int_0^1 x\\,dx");
+
+    let options = ConversionOptions { math: MathMode::Placeholder(String::from("[formula]")), ..ConversionOptions::plain_text() };
+    assert_eq!(parse_html_with_options(html, &options), "Area [formula] of a circle.\n\n[formula]");
+}
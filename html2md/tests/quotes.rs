@@ -1,6 +1,8 @@
 extern crate html2md;
 
-use html2md::parse_html;
+mod common;
+
+use common::{parse_html, parse_html_presets};
 use pretty_assertions::assert_eq;
 use indoc::indoc;
 
@@ -40,12 +42,20 @@ fn test_details() {
         <p>Than are dreamt of in your philosophy</p>
     </details>
     "};
-    let md = parse_html(&html);
-    assert_eq!(md, "<details> <summary>There are more things in heaven and Earth, **Horatio**</summary>\n\nThan are dreamt of in your philosophy\n\n</details>")
+    let (md, plain) = parse_html_presets(&html);
+    assert_eq!(md, "**There are more things in heaven and Earth, Horatio**\n\nThan are dreamt of in your philosophy");
+    assert_eq!(plain, "There are more things in heaven and Earth, Horatio\n\nThan are dreamt of in your philosophy")
 }
 
 #[test]
 fn test_subsup() {
     let md = parse_html("X<sub>2</sub>");
     assert_eq!(md, r#"X<sub>2</sub>"#)
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_inline_quotes() {
+    let (md, plain) = parse_html_presets(r#"<p>He said <q>she told me <q>no</q> twice</q> and left, see <cite>Hamlet</cite>.</p>"#);
+    assert_eq!(md, "He said “she told me ‘no’ twice” and left, see *Hamlet*.");
+    assert_eq!(plain, "He said “she told me ‘no’ twice” and left, see Hamlet.")
+}
@@ -0,0 +1,28 @@
+extern crate html2md;
+
+mod common;
+
+use common::parse_html;
+use html2md::{parse_html_with_options, ConversionOptions, RubyMode};
+use pretty_assertions::assert_eq;
+
+const HTML: &str = "<p>これは<ruby>漢<rp>(</rp><rt>かん</rt><rp>)</rp>字<rp>(</rp><rt>じ</rt><rp>)</rp></ruby>と\
+    <ruby><rb>東</rb><rb>京</rb><rt>とう</rt><rt>きょう</rt></ruby>と<ruby>日本<rt>にほん</rt>語</ruby>です。</p>";
+
+#[test]
+fn test_ruby_base() {
+    assert_eq!(parse_html(HTML), "これは漢字と東京と日本語です。");
+}
+
+#[test]
+fn test_ruby_modes() {
+    for (mode, expected) in [
+        (RubyMode::Parenthesized, "これは漢(かん)字(じ)と東(とう)京(きょう)と日本(にほん)語です。"),
+        (RubyMode::Reading, "これはかんじととうきょうとにほん語です。"),
+    ].iter() {
+        for options in [ConversionOptions::markdown(), ConversionOptions::plain_text()].iter() {
+            let options = ConversionOptions { ruby: *mode, ..options.clone() };
+            assert_eq!(parse_html_with_options(HTML, &options), *expected);
+        }
+    }
+}
@@ -1,17 +1,20 @@
 extern crate html2md;
 
-use html2md::parse_html;
+mod common;
+
+use common::parse_html_presets;
 use pretty_assertions::assert_eq;
 
 #[test]
 fn test_styles_with_spaces() {
-    let md = parse_html(r#"It read:<s> Nobody will ever love you</s>"#);
-    assert_eq!(md, r#"It read: ~~Nobody will ever love you~~"#)
+    let (md, plain) = parse_html_presets(r#"It read:<s> Nobody will ever love you</s>"#);
+    assert_eq!(md, r#"It read: ~~Nobody will ever love you~~"#);
+    assert_eq!(plain, "It read: Nobody will ever love you")
 }
 
 #[test]
 fn test_styles_with_newlines() {
-    let md = parse_html(r#"
+    let (md, plain) = parse_html_presets(r#"
 And she said:<br/>
 <s>We are all just prisoners here<br/>
 <u> Of our own device<br/>  </s>
@@ -28,5 +31,13 @@ And she said:
 And in the master's chambers  
 They gathered for the feast  
 *They stab it with their steely knives*  
-**But they just can't kill the beast**")
+**But they just can't kill the beast**");
+    assert_eq!(plain, "\
+And she said:  
+We are all just prisoners here  
+ Of our own device  
+And in the master's chambers  
+They gathered for the feast  
+They stab it with their steely knives  
+But they just can't kill the beast")
 }
\ No newline at end of file
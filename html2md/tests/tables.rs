@@ -1,11 +1,13 @@
 extern crate html2md;
 
-use html2md::parse_html;
+mod common;
+
+use common::{parse_markdown, parse_html_presets};
 use pretty_assertions::assert_eq;
 
 #[test]
 fn test_tables() {
-    let md = parse_html(r#"<table>
+    let md = parse_markdown(r#"<table>
   <thead>
     <tr>
       <th scope='col'>Minor1</th>
@@ -32,7 +34,7 @@ fn test_tables() {
 
 #[test]
 fn test_tables_invalid_more_headers() {
-    let md = parse_html(r#"<table>
+    let md = parse_markdown(r#"<table>
   <thead>
     <tr>
       <th scope='col'>Minor1</th>
@@ -61,7 +63,7 @@ fn test_tables_invalid_more_headers() {
 
 #[test]
 fn test_tables_invalid_more_rows() {
-    let md = parse_html(r#"<table>
+    let md = parse_markdown(r#"<table>
   <thead>
     <tr>
       <th scope='col'>Minor1</th>
@@ -86,7 +88,7 @@ fn test_tables_invalid_more_rows() {
 
 #[test]
 fn test_tables_odd_column_width() {
-    let md = parse_html(r#"<table>
+    let md = parse_markdown(r#"<table>
   <thead>
     <tr>
       <th scope='col'>Minor</th>
@@ -109,7 +111,7 @@ fn test_tables_odd_column_width() {
 
 #[test]
 fn test_tables_alignment() {
-    let md = parse_html(r#"<table>
+    let md = parse_markdown(r#"<table>
   <thead>
     <tr>
       <th align='right'>Minor1</th>
@@ -136,7 +138,7 @@ fn test_tables_alignment() {
 
 #[test]
 fn test_tables_wild_example() {
-    let md = parse_html(r#"
+    let md = parse_markdown(r#"
 <table style="width: 100%;">
     <thead>
     <tr>
@@ -191,4 +193,130 @@ fn test_tables_wild_example() {
 |    One ring to find them    |   And two for the joy    |You're the pride of White Star Line|   |   |   |
 | One ring to bring them all  | And three for the girls  |      Roll on, Titanic, roll       |   |   |   |
 |And in the darkness bind them|  And four for the boys   |      Into the mists of time       |   |   |   |");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_tables_spans() {
+    let md = parse_markdown(r#"<table>
+  <tr><th>Team</th><th colspan="2">Score</th></tr>
+  <tr><td rowspan="2">Lions</td><td>1</td><td>2</td></tr>
+  <tr><td>3</td><td>4</td></tr>
+  <tr><td>Bears</td><td colspan="2">n/a</td></tr>
+</table>"#);
+
+    assert_eq!(md, "\
+|Team |Score|Score|
+|-----|-----|-----|
+|Lions|  1  |  2  |
+|     |  3  |  4  |
+|Bears| n/a |     |");
+}
+
+#[test]
+fn test_tables_multiple_header_rows_and_footer() {
+    let md = parse_markdown(r#"<table>
+  <caption>Quarterly results</caption>
+  <tfoot><tr><td>Total</td><td>30</td><td>40</td></tr></tfoot>
+  <thead>
+    <tr><th rowspan="2">Region</th><th colspan="2">2024</th></tr>
+    <tr><th>Q1</th><th>Q2</th></tr>
+  </thead>
+  <tbody>
+    <tr><td>North</td><td>10</td><td>15</td></tr>
+    <tr><td>South</td><td>20</td><td>25</td></tr>
+  </tbody>
+</table>"#);
+
+    assert_eq!(md, "\
+Quarterly results
+
+|Region|2024 Q1|2024 Q2|
+|------|-------|-------|
+|North |  10   |  15   |
+|South |  20   |  25   |
+|Total |  30   |  40   |");
+}
+
+#[test]
+fn test_tables_nested() {
+    let md = parse_markdown(r#"<table>
+  <tr><th>Name</th><th>Details</th></tr>
+  <tr><td>Outer</td><td>See below<table><tr><th>Inner</th></tr><tr><td>cell</td></tr></table></td></tr>
+</table>"#);
+
+    assert_eq!(md, "\
+|Name | Details |
+|-----|---------|
+|Outer|See below|
+
+|Inner|
+|-----|
+|cell |");
+}
+
+#[test]
+fn test_tables_linear() {
+    let (md, plain) = parse_html_presets(r#"<p>Largest countries of the EU</p><table>
+  <caption>Population</caption>
+  <thead><tr><th>Country</th><th>Population</th></tr></thead>
+  <tbody>
+    <tr><td>France</td><td>68M</td></tr>
+    <tr><td>Germany</td><td><b>84M</b></td></tr>
+  </tbody>
+</table>"#);
+
+    assert_eq!(md, "\
+Largest countries of the EU
+
+Population
+
+|Country|Population|
+|-------|----------|
+|France |   68M    |
+|Germany| **84M**  |");
+    assert_eq!(plain, "\
+Largest countries of the EU
+
+Population. Country: France; Population: 68M. Country: Germany; Population: 84M.");
+}
+
+#[test]
+fn test_tables_linear_spans() {
+    let (_, plain) = parse_html_presets(r#"<table>
+  <thead>
+    <tr><th rowspan="2">Team</th><th colspan="2">Score</th></tr>
+    <tr><th>Home</th><th>Away</th></tr>
+  </thead>
+  <tr><td rowspan="2">Lions</td><td>1</td><td>2</td></tr>
+  <tr><td>3</td><td></td></tr>
+  <tr><td>Bears</td><td colspan="2">n/a</td></tr>
+</table>"#);
+
+    assert_eq!(plain, "\
+Team: Lions; Score Home: 1; Score Away: 2. Team: Lions; Score Home: 3. Team: Bears; Score Home: n/a.");
+}
+
+#[test]
+fn test_tables_linear_without_header() {
+    let (_, plain) = parse_html_presets(r#"<table>
+  <tr><td>Paris</td><td>2.1M</td></tr>
+  <tr><td>Marseille</td><td>Second largest<br>city</td></tr>
+</table><table><tr><td>Outer<table><tr><td>Inner</td></tr></table></td></tr></table>"#);
+
+    assert_eq!(plain, "\
+Paris; 2.1M. Marseille; Second largest city.
+
+Outer.
+
+Inner.");
+}
+
+#[test]
+fn test_tables_linear_header_only() {
+    let (_, plain) = parse_html_presets(r#"<table><thead><tr><th>Name</th><th>Age</th></tr></thead></table><p>After</p>"#);
+
+    assert_eq!(plain, "\
+Name; Age.
+
+After");
+}
@@ -1,8 +1,17 @@
 extern crate html2md;
 
-use html2md::parse_html;
+mod common;
+
+use common::{parse_html, parse_html_presets};
 use pretty_assertions::assert_eq;
 
+#[test]
+fn test_default_is_plain_text() {
+    let html = r#"<h1>Title</h1><p>Some <em>text</em> with <a href="https://example.com">a link</a>.</p>"#;
+    assert_eq!(html2md::parse_html(html), "Title\n\nSome text with a link.");
+    assert_eq!(html2md::parse_html(html), html2md::parse_html_with_options(html, &html2md::ConversionOptions::default()));
+}
+
 #[test]
 fn test_dumb() {
     let md = parse_html("<p>CARTHAPHILUS</p>");
@@ -11,29 +20,33 @@ fn test_dumb() {
 
 #[test]
 fn test_anchor() {
-    let md = parse_html(r#"<p><a href="http://ya.ru">APOSIMZ</a></p>"#);
-    assert_eq!(md, "[APOSIMZ](http://ya.ru)")
+    let (md, plain) = parse_html_presets(r#"<p><a href="http://ya.ru">APOSIMZ</a></p>"#);
+    assert_eq!(md, "[APOSIMZ](http://ya.ru)");
+    assert_eq!(plain, "APOSIMZ")
 }
 
 #[test]
 fn test_anchor2() {
-    let md = parse_html(r#"<p><a href="http://ya.ru">APOSIMZ</a><a href="http://yandex.ru">SIDONIA</a></p>"#);
-    assert_eq!(md, "[APOSIMZ](http://ya.ru)[SIDONIA](http://yandex.ru)")
+    let (md, plain) = parse_html_presets(r#"<p><a href="http://ya.ru">APOSIMZ</a><a href="http://yandex.ru">SIDONIA</a></p>"#);
+    assert_eq!(md, "[APOSIMZ](http://ya.ru)[SIDONIA](http://yandex.ru)");
+    assert_eq!(plain, "APOSIMZSIDONIA")
 }
 
 #[test]
 fn test_anchor3() {
-    let md = parse_html(r#"<p><a href="http://ya.ru">APOSIMZ</a><p/><a href="http://yandex.ru">SIDONIA</a></p>"#);
+    let (md, plain) = parse_html_presets(r#"<p><a href="http://ya.ru">APOSIMZ</a><p/><a href="http://yandex.ru">SIDONIA</a></p>"#);
     assert_eq!(md, "\
 [APOSIMZ](http://ya.ru)
 
-[SIDONIA](http://yandex.ru)")
+[SIDONIA](http://yandex.ru)");
+    assert_eq!(plain, "APOSIMZ\n\nSIDONIA")
 }
 
 #[test]
 fn test_image() {
-    let md = parse_html(r#"<p><a href="https://gitter.im/MARC-FS/Lobby?utm_source=badge&amp;utm_medium=badge&amp;utm_campaign=pr-badge&amp;utm_content=badge"><img src="https://img.shields.io/gitter/room/MARC-FS/MARC-FS.svg" alt="Gitter"></a><br>"#);
-    assert_eq!(md, "[![Gitter](https://img.shields.io/gitter/room/MARC-FS/MARC-FS.svg)](https://gitter.im/MARC-FS/Lobby?utm_source=badge&utm_medium=badge&utm_campaign=pr-badge&utm_content=badge)")
+    let (md, plain) = parse_html_presets(r#"<p><a href="https://gitter.im/MARC-FS/Lobby?utm_source=badge&amp;utm_medium=badge&amp;utm_campaign=pr-badge&amp;utm_content=badge"><img src="https://img.shields.io/gitter/room/MARC-FS/MARC-FS.svg" alt="Gitter"></a><br>"#);
+    assert_eq!(md, "[![Gitter](https://img.shields.io/gitter/room/MARC-FS/MARC-FS.svg)](https://gitter.im/MARC-FS/Lobby?utm_source=badge&utm_medium=badge&utm_campaign=pr-badge&utm_content=badge)");
+    assert_eq!(plain, "")
 }
 
 #[test]
@@ -44,14 +57,16 @@ fn test_escaping() {
 
 #[test]
 fn test_escaping_mid_hyphens() {
-    let md = parse_html(r#"<h1>This is a header with-hyphen!</h1>"#);
-    assert_eq!(md, "This is a header with-hyphen!\n==========")
+    let (md, plain) = parse_html_presets(r#"<h1>This is a header with-hyphen!</h1>"#);
+    assert_eq!(md, "This is a header with-hyphen!\n==========");
+    assert_eq!(plain, "This is a header with-hyphen!")
 }
 
 #[test]
 fn test_escaping_start_hyphens() {
-    let md = parse_html(r#"<h1>- This is a header with starting hyphen!</h1>"#);
-    assert_eq!(md, "\\- This is a header with starting hyphen!\n==========")
+    let (md, plain) = parse_html_presets(r#"<h1>- This is a header with starting hyphen!</h1>"#);
+    assert_eq!(md, "\\- This is a header with starting hyphen!\n==========");
+    assert_eq!(plain, "\\- This is a header with starting hyphen!")
 }
 
 #[test]
@@ -63,8 +78,9 @@ fn test_escaping_start_sharp() {
 /// Note: Also strips multiple spaces
 #[test]
 fn test_escaping_start_hyphens_space() {
-    let md = parse_html(r#"<h1>   - This is a header with starting hyphen!</h1>"#);
-    assert_eq!(md, " \\- This is a header with starting hyphen!\n==========")
+    let (md, plain) = parse_html_presets(r#"<h1>   - This is a header with starting hyphen!</h1>"#);
+    assert_eq!(md, " \\- This is a header with starting hyphen!\n==========");
+    assert_eq!(plain, " \\- This is a header with starting hyphen!")
 }
 
 #[test]
@@ -75,7 +91,7 @@ fn test_escaping_html_tags() {
 
 #[test]
 fn test_headers() {
-    let md = parse_html(r#"<h1 id="marc-fs">MARC-FS</h1><p><a href="http://Mail.ru">Mail.ru</a> Cloud filesystem written for FUSE</p><h2 id="synopsis">Synopsis</h2>"#);
+    let (md, plain) = parse_html_presets(r#"<h1 id="marc-fs">MARC-FS</h1><p><a href="http://Mail.ru">Mail.ru</a> Cloud filesystem written for FUSE</p><h2 id="synopsis">Synopsis</h2>"#);
     assert_eq!(md, "\
 MARC-FS
 ==========
@@ -83,7 +99,8 @@ MARC-FS
 [Mail.ru](http://Mail.ru) Cloud filesystem written for FUSE
 
 Synopsis
-----------")
+----------");
+    assert_eq!(plain, "MARC-FS\n\nMail.ru Cloud filesystem written for FUSE\n\nSynopsis")
 }
 
 #[test]
@@ -0,0 +1,102 @@
+#ifndef READABILITY_TEXT_CLEANUP_H
+#define READABILITY_TEXT_CLEANUP_H
+
+/* Generated by cbindgen from src/ffi.rs, run `make header` to update. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Text kept for ruby annotations, such as the furigana of Japanese text
+typedef enum RtcRuby {
+  // Base text only
+  RTC_RUBY_BASE = 0,
+  // Base text followed by its reading in parentheses
+  RTC_RUBY_PARENTHESIZED = 1,
+  // Reading in place of the annotated base text
+  RTC_RUBY_READING = 2,
+} RtcRuby;
+
+// Result code of the fallible functions
+typedef enum RtcStatus {
+  RTC_STATUS_OK = 0,
+  // A required pointer argument was NULL
+  RTC_STATUS_NULL_ARGUMENT = 1,
+  // A string argument was not valid UTF-8
+  RTC_STATUS_INVALID_UTF8 = 2,
+  // The result contains a NUL character and can't be returned as a C string
+  RTC_STATUS_INTERIOR_NUL = 3,
+  // The pipeline panicked, this is a bug. Only builds with unwinding panics, such as
+  // `make capi`, return it: release builds abort
+  RTC_STATUS_PANIC = 4,
+} RtcStatus;
+
+// Switches for the optional steps of the cleanup pipeline,
+// obtain the defaults with `rtc_options_default`
+typedef struct RtcOptions {
+  // Rewrite common abbreviations ("e.g.", "Dr.") so that their dots don't end sentences
+  bool replace_abbreviations;
+  // Remove numeric citation markers such as `[12]`
+  bool strip_citations;
+  // Leave out the content of `nav`, `aside`, `form` and `footer` elements, mostly site navigation and chrome
+  bool drop_boilerplate;
+  // Keep the captions of figures and tables, as paragraphs of their own
+  bool captions;
+  // NULL, or a NUL-terminated UTF-8 token written in place of every formula, such as `[MATH]`.
+  // Formulas are written as linear text when NULL
+  const char *math_placeholder;
+  // Text kept for ruby annotations
+  enum RtcRuby ruby;
+} RtcOptions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Options used by `prepare_text`
+struct RtcOptions rtc_options_default(void);
+
+// Cleans up a NUL-terminated UTF-8 HTML document.
+//
+// On success `*out` receives the cleaned text, free it with `rtc_string_free`.
+// On failure `*out` is set to NULL. `options` may be NULL to use the defaults.
+//
+// # Safety
+// `html` must be NULL or point to a NUL-terminated string, `options` must be NULL
+// or point to a valid `RtcOptions`, whose `math_placeholder` is NULL or a NUL-terminated
+// string, and `out` must be NULL or valid for writes.
+enum RtcStatus rtc_prepare_text(const char *html, const struct RtcOptions *options, char **out);
+
+// Cleans up an HTML document given as `len` raw bytes in any encoding.
+//
+// `hint` is NULL, a charset label or the `Content-Type` header the document was
+// served with. Ownership rules are the same as for `rtc_prepare_text`.
+//
+// # Safety
+// `bytes` must point to at least `len` readable bytes, `hint` must be NULL or point
+// to a NUL-terminated string, `options` and `out` as in `rtc_prepare_text`.
+enum RtcStatus rtc_prepare_bytes(const uint8_t *bytes,
+                                 size_t len,
+                                 const char *hint,
+                                 const struct RtcOptions *options,
+                                 char **out);
+
+// Releases a string returned by this library. Passing NULL is a no-op.
+//
+// # Safety
+// `string` must be NULL or a pointer obtained from this library that wasn't freed yet.
+void rtc_string_free(char *string);
+
+// Describes the last failure on the calling thread, NULL if the last call succeeded.
+//
+// The returned string is owned by the library and stays valid until the next call
+// into this library from the same thread.
+const char *rtc_last_error_message(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* READABILITY_TEXT_CLEANUP_H */
@@ -0,0 +1,141 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use ::readability_text_cleanup as cleanup;
+
+/// Switches for the optional steps of the cleanup pipeline
+#[pyclass(module = "readability_text_cleanup")]
+#[derive(Clone)]
+struct Options {
+    /// Rewrite common abbreviations ("e.g.", "Dr.") so that their dots don't end sentences
+    #[pyo3(get, set)]
+    replace_abbreviations: bool,
+    /// Remove numeric citation markers such as `[12]`
+    #[pyo3(get, set)]
+    strip_citations: bool,
+    /// Leave out the content of `nav`, `aside`, `form` and `footer` elements, mostly site navigation and chrome
+    #[pyo3(get, set)]
+    drop_boilerplate: bool,
+    /// Keep the captions of figures and tables, as paragraphs of their own
+    #[pyo3(get, set)]
+    captions: bool,
+    /// Token written in place of every formula, such as `[MATH]`. Formulas are written as linear text when `None`
+    #[pyo3(get, set)]
+    math_placeholder: Option<String>,
+    /// Text kept for ruby annotations: `"base"`, `"parenthesized"` (the base text followed by its reading) or `"reading"`
+    #[pyo3(get)]
+    ruby: String,
+}
+
+#[pymethods]
+impl Options {
+    #[new]
+    #[pyo3(signature = (*, replace_abbreviations = true, strip_citations = true, drop_boilerplate = false, captions = true, math_placeholder = None, ruby = "base".to_string()))]
+    fn new(
+        replace_abbreviations: bool,
+        strip_citations: bool,
+        drop_boilerplate: bool,
+        captions: bool,
+        math_placeholder: Option<String>,
+        ruby: String,
+    ) -> PyResult<Self> {
+        ruby_text(&ruby)?;
+        Ok(Options { replace_abbreviations, strip_citations, drop_boilerplate, captions, math_placeholder, ruby })
+    }
+
+    #[setter]
+    fn set_ruby(&mut self, ruby: String) -> PyResult<()> {
+        ruby_text(&ruby)?;
+        self.ruby = ruby;
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Options(replace_abbreviations={}, strip_citations={}, drop_boilerplate={}, captions={}, math_placeholder={}, ruby={})",
+            py_bool(self.replace_abbreviations),
+            py_bool(self.strip_citations),
+            py_bool(self.drop_boilerplate),
+            py_bool(self.captions),
+            self.math_placeholder.as_deref().map_or("None".to_string(), py_str),
+            py_str(&self.ruby)
+        )
+    }
+}
+
+fn to_options(options: Option<Options>) -> cleanup::Options {
+    match options {
+        Some(options) => cleanup::Options {
+            replace_abbreviations: options.replace_abbreviations,
+            strip_citations: options.strip_citations,
+            drop_boilerplate: options.drop_boilerplate,
+            captions: options.captions,
+            math_placeholder: options.math_placeholder,
+            // validated when set
+            ruby: ruby_text(&options.ruby).unwrap_or_default(),
+        },
+        None => cleanup::Options::default(),
+    }
+}
+
+fn ruby_text(ruby: &str) -> PyResult<cleanup::RubyText> {
+    match ruby {
+        "base" => Ok(cleanup::RubyText::Base),
+        "parenthesized" => Ok(cleanup::RubyText::Parenthesized),
+        "reading" => Ok(cleanup::RubyText::Reading),
+        _ => Err(PyValueError::new_err(format!("ruby must be \"base\", \"parenthesized\" or \"reading\", not {:?}", ruby))),
+    }
+}
+
+fn py_bool(value: bool) -> &'static str {
+    if value { "True" } else { "False" }
+}
+
+fn py_str(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Cleans up an HTML document, paragraphs are separated by blank lines
+#[pyfunction]
+#[pyo3(signature = (text, options = None))]
+fn prepare_text(py: Python<'_>, text: &str, options: Option<Options>) -> String {
+    let options = to_options(options);
+    py.allow_threads(|| cleanup::prepare_text_with_options(text, &options))
+}
+
+/// Cleans up an HTML document given as bytes in any encoding.
+///
+/// `hint` is a charset label or the `Content-Type` header the page was served with.
+#[pyfunction]
+#[pyo3(signature = (data, hint = None, options = None))]
+fn prepare_bytes(py: Python<'_>, data: &[u8], hint: Option<&str>, options: Option<Options>) -> String {
+    let options = to_options(options);
+    py.allow_threads(|| cleanup::prepare_bytes_with_options(data, hint, &options))
+}
+
+/// Cleans up an HTML document and returns its paragraphs as lists of sentences
+#[pyfunction]
+#[pyo3(signature = (text, options = None))]
+fn split_sentences(py: Python<'_>, text: &str, options: Option<Options>) -> Vec<Vec<String>> {
+    let options = to_options(options);
+    py.allow_threads(|| cleanup::prepare_paragraphs(text, &options))
+}
+
+/// Cleans up many HTML documents in parallel, results keep the order of `texts`
+#[pyfunction]
+#[pyo3(signature = (texts, options = None))]
+fn prepare_batch(py: Python<'_>, texts: Vec<String>, options: Option<Options>) -> Vec<String> {
+    let options = to_options(options);
+    py.allow_threads(|| cleanup::prepare_batch(&texts, &options))
+}
+
+#[pymodule]
+#[pyo3(name = "readability_text_cleanup")]
+fn readability_text_cleanup_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Options>()?;
+    m.add_function(wrap_pyfunction!(prepare_text, m)?)?;
+    m.add_function(wrap_pyfunction!(prepare_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(split_sentences, m)?)?;
+    m.add_function(wrap_pyfunction!(prepare_batch, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+"""Tests for the Python bindings, run after `maturin develop` with `python -m unittest discover python/tests`"""
+
+import pathlib
+import threading
+import unittest
+
+import readability_text_cleanup as rtc
+
+# shared with the Rust and Node.js test suites
+FIXTURES = pathlib.Path(__file__).resolve().parents[2] / "tests" / "fixtures"
+
+
+def fixtures():
+    for html in sorted(FIXTURES.glob("*.html")):
+        yield html.stem, html.read_text(encoding="utf-8"), html.with_suffix(".txt").read_text(encoding="utf-8").rstrip()
+
+
+class FixtureTest(unittest.TestCase):
+    def test_fixtures_match_rust(self):
+        cases = list(fixtures())
+        self.assertTrue(cases)
+        for name, html, expected in cases:
+            with self.subTest(fixture=name):
+                self.assertEqual(expected, rtc.prepare_text(html))
+
+    def test_batch_matches_single(self):
+        cases = list(fixtures())
+        results = rtc.prepare_batch([html for _, html, _ in cases])
+        self.assertEqual([expected for _, _, expected in cases], results)
+
+    def test_sentences_join_to_text(self):
+        for name, html, expected in fixtures():
+            with self.subTest(fixture=name):
+                paragraphs = rtc.split_sentences(html)
+                self.assertEqual(expected, "\n\n".join(" ".join(sentences) for sentences in paragraphs))
+
+
+class ApiTest(unittest.TestCase):
+    def test_options(self):
+        options = rtc.Options(strip_citations=False)
+        self.assertTrue(options.replace_abbreviations)
+        self.assertFalse(options.strip_citations)
+        self.assertEqual("Options(replace_abbreviations=True, strip_citations=False, drop_boilerplate=False, captions=True, math_placeholder=None, ruby='base')", repr(options))
+        self.assertEqual("See here[1].", rtc.prepare_text("<p>See here[1].</p>", options))
+        self.assertEqual("See here.", rtc.prepare_text("<p>See here[1].</p>"))
+        page = "<nav>Home</nav><p>Text.</p>"
+        self.assertEqual("Text.", rtc.prepare_text(page, rtc.Options(drop_boilerplate=True)))
+        figure = "<figure><img src='chart.png'><figcaption>Sales.</figcaption></figure><p>Text.</p>"
+        self.assertEqual("Text.", rtc.prepare_text(figure, rtc.Options(captions=False)))
+        ruby = "<p><ruby>漢字<rt>かんじ</rt></ruby></p>"
+        self.assertEqual("漢字(かんじ)", rtc.prepare_text(ruby, rtc.Options(ruby="parenthesized")))
+        with self.assertRaises(ValueError):
+            rtc.Options(ruby="furigana")
+        formula = "<p>Solve <math><mi>x</mi></math> now.</p>"
+        self.assertEqual("Solve [MATH] now.", rtc.prepare_text(formula, rtc.Options(math_placeholder="[MATH]")))
+
+    def test_split_sentences(self):
+        self.assertEqual(
+            [["First sentence.", "Second one!"], ["Third?"]],
+            rtc.split_sentences("<p>First sentence. Second one!</p><p>Third?</p>"),
+        )
+
+    def test_prepare_bytes(self):
+        data = "<p>Café crème.</p>".encode("cp1252")
+        self.assertEqual("Café crème.", rtc.prepare_bytes(data, "text/html; charset=windows-1252"))
+        self.assertEqual("Café crème.", rtc.prepare_bytes(data))
+
+    def test_concurrent_calls(self):
+        html = "<p>" + "A reasonably long sentence about nothing in particular. " * 2000 + "</p>"
+        results = [None] * 4
+
+        def work(index):
+            results[index] = rtc.prepare_text(html)
+
+        threads = [threading.Thread(target=work, args=(index,)) for index in range(len(results))]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        self.assertEqual(1, len(set(results)))
+
+
+if __name__ == "__main__":
+    unittest.main()
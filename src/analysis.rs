@@ -0,0 +1,307 @@
+//! Style annotations in the manner of the Hemingway editor: very long sentences, passive
+//! voice, adverbs, qualifiers and words with simpler alternatives.
+//!
+//! Sentences are the ones of `prepare_document`. Offsets count Unicode characters of the
+//! cleaned text. The default word lists are English.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::metrics::word_ranges;
+use super::Options;
+
+/// Kind of writing issue an annotation points at
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Category {
+    VeryLongSentence,
+    Passive,
+    Adverb,
+    Qualifier,
+    SimplerAlternative,
+}
+
+/// A span of the cleaned text with a writing issue
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    pub category: Category,
+    pub start: usize,
+    pub end: usize,
+    /// Replacement for `SimplerAlternative` annotations
+    pub suggestion: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotatedSentence {
+    pub start: usize,
+    pub end: usize,
+    pub words: usize,
+    pub annotations: Vec<Annotation>,
+}
+
+/// Cleaned text with its sentences and their annotations
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Analysis {
+    /// The same text `prepare_text_with_options` returns
+    pub text: String,
+    pub sentences: Vec<AnnotatedSentence>,
+}
+
+/// Thresholds and word lists of the analysis, all words and phrases are matched case insensitively
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AnalysisSettings {
+    /// Sentences with more words are very long
+    pub very_long_sentence_words: usize,
+    /// Words ending in `-ly` that are not adverbs
+    pub adverb_exceptions: Vec<String>,
+    /// Hedging words and phrases
+    pub qualifiers: Vec<String>,
+    /// Words and phrases mapped to a simpler alternative
+    pub simpler_alternatives: HashMap<String, String>,
+}
+
+impl Default for AnalysisSettings {
+    fn default() -> Self {
+        let words = |words: &[&str]| words.iter().map(|word| word.to_string()).collect();
+        AnalysisSettings {
+            very_long_sentence_words: 25,
+            adverb_exceptions: words(&[
+                "only", "family", "reply", "early", "july", "italy", "apply", "supply", "fly", "belly", "holy", "ugly", "rely", "ally",
+                "bully", "daily", "weekly", "monthly", "yearly", "friendly", "lovely", "likely", "lonely", "silly", "elderly", "costly",
+                "curly", "jelly", "rally", "assembly", "anomaly", "butterfly", "imply", "comply", "multiply", "homily",
+            ]),
+            qualifiers: words(&[
+                "i think", "i believe", "i suppose", "i guess", "in my opinion", "it seems", "it appears", "maybe", "perhaps", "probably",
+                "possibly", "arguably", "kind of", "sort of", "a bit", "a little", "somewhat", "quite", "rather", "fairly", "pretty much",
+                "more or less", "to some extent", "just", "really", "very", "basically", "virtually",
+            ]),
+            simpler_alternatives: [
+                ("a number of", "some"),
+                ("accomplish", "do"),
+                ("additional", "more"),
+                ("approximately", "about"),
+                ("ascertain", "find out"),
+                ("assist", "help"),
+                ("at this point in time", "now"),
+                ("commence", "start"),
+                ("demonstrate", "show"),
+                ("due to the fact that", "because"),
+                ("endeavor", "try"),
+                ("facilitate", "help"),
+                ("in order to", "to"),
+                ("in the event that", "if"),
+                ("indicate", "show"),
+                ("individual", "person"),
+                ("methodology", "method"),
+                ("modify", "change"),
+                ("numerous", "many"),
+                ("objective", "goal"),
+                ("obtain", "get"),
+                ("prior to", "before"),
+                ("purchase", "buy"),
+                ("regarding", "about"),
+                ("request", "ask"),
+                ("require", "need"),
+                ("subsequently", "later"),
+                ("sufficient", "enough"),
+                ("terminate", "end"),
+                ("utilize", "use"),
+            ]
+            .iter()
+            .map(|(phrase, alternative)| (phrase.to_string(), alternative.to_string()))
+            .collect(),
+        }
+    }
+}
+
+/// Forms of "to be" starting a passive construction
+const BE_FORMS: [&str; 8] = ["am", "is", "are", "was", "were", "be", "been", "being"];
+
+/// Past participles not ending in `-ed`
+const IRREGULAR_PARTICIPLES: [&str; 52] = [
+    "beaten", "begun", "bitten", "blown", "born", "bought", "broken", "brought", "built", "caught", "chosen", "done", "drawn", "driven",
+    "eaten", "fallen", "felt", "forgiven", "forgotten", "found", "frozen", "given", "grown", "heard", "held", "hidden", "hit", "kept",
+    "known", "laid", "led", "left", "lost", "made", "meant", "met", "paid", "read", "said", "seen", "sent", "shown", "sold", "spoken",
+    "stolen", "taken", "taught", "thrown", "told", "understood", "won", "written",
+];
+
+/// Cleans up `html` and annotates the writing issues of every sentence
+pub fn analyze(html: &str, options: &Options, settings: &AnalysisSettings) -> Analysis {
+    let document = super::prepare_document(html, options);
+
+    let sentences = document
+        .paragraphs
+        .iter()
+        .flat_map(|paragraph| &paragraph.sentences)
+        .map(|sentence| {
+            let start = sentence.start;
+            let tokens = tokens(&sentence.text);
+            let mut annotations = annotate(&tokens, settings);
+            for annotation in &mut annotations {
+                annotation.start += start;
+                annotation.end += start;
+            }
+            let end = sentence.end;
+            if tokens.len() > settings.very_long_sentence_words {
+                annotations.insert(0, Annotation { category: Category::VeryLongSentence, start, end, suggestion: None });
+            }
+            AnnotatedSentence { start, end, words: tokens.len(), annotations }
+        })
+        .collect();
+
+    Analysis { text: document.text, sentences }
+}
+
+/// Lowercased word with its character offsets in the sentence
+pub(crate) struct Token {
+    pub word: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Words of `metrics::words`, with offsets in characters
+pub(crate) fn tokens(sentence: &str) -> Vec<Token> {
+    let (mut byte, mut chars) = (0, 0);
+    let mut offset = |to: usize| {
+        chars += sentence[byte..to].chars().count();
+        byte = to;
+        chars
+    };
+    word_ranges(sentence)
+        .map(|range| Token { word: sentence[range.clone()].to_lowercase(), start: offset(range.start), end: offset(range.end) })
+        .collect()
+}
+
+/// Annotations of the words and phrases of a sentence, offsets relative to the sentence
+fn annotate(tokens: &[Token], settings: &AnalysisSettings) -> Vec<Annotation> {
+    let mut annotations = vec![];
+    let span = |category, first: &Token, last: &Token, suggestion: Option<&String>| Annotation {
+        category,
+        start: first.start,
+        end: last.end,
+        suggestion: suggestion.cloned(),
+    };
+
+    let mut index = 0;
+    while index < tokens.len() {
+        if BE_FORMS.contains(&tokens[index].word.as_str()) {
+            // "was written", "is not used", "were quickly forgotten"
+            let mut next = index + 1;
+            if tokens.get(next).is_some_and(|token| token.word == "not" || is_adverb(&token.word, settings)) {
+                next += 1;
+            }
+            if let Some(participle) = tokens.get(next).filter(|token| is_participle(&token.word)) {
+                annotations.push(span(Category::Passive, &tokens[index], participle, None));
+                index = next + 1;
+                continue;
+            }
+        }
+        index += 1;
+    }
+
+    for token in tokens.iter().filter(|token| is_adverb(&token.word, settings)) {
+        annotations.push(span(Category::Adverb, token, token, None));
+    }
+
+    for (category, phrases) in [
+        (Category::Qualifier, settings.qualifiers.iter().map(|phrase| (phrase, None)).collect::<Vec<_>>()),
+        (
+            Category::SimplerAlternative,
+            settings.simpler_alternatives.iter().map(|(phrase, alternative)| (phrase, Some(alternative))).collect(),
+        ),
+    ] {
+        let mut phrases: Vec<(Vec<String>, Option<&String>)> = phrases
+            .into_iter()
+            .map(|(phrase, suggestion)| (phrase.to_lowercase().split_whitespace().map(str::to_string).collect(), suggestion))
+            .filter(|(words, _): &(Vec<String>, _)| !words.is_empty())
+            .collect();
+        // longest phrases first, alphabetical among equally long ones for stable results
+        phrases.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let mut index = 0;
+        while index < tokens.len() {
+            let matched = phrases.iter().find(|(words, _)| {
+                tokens.len() - index >= words.len() && words.iter().zip(&tokens[index..]).all(|(word, token)| *word == token.word)
+            });
+            match matched {
+                Some((words, suggestion)) => {
+                    annotations.push(span(category, &tokens[index], &tokens[index + words.len() - 1], *suggestion));
+                    index += words.len();
+                }
+                None => index += 1,
+            }
+        }
+    }
+
+    annotations.sort_by_key(|annotation| (annotation.start, annotation.end));
+    annotations
+}
+
+fn is_adverb(word: &str, settings: &AnalysisSettings) -> bool {
+    word.chars().count() > 4 && word.ends_with("ly") && !settings.adverb_exceptions.iter().any(|exception| exception.eq_ignore_ascii_case(word))
+}
+
+fn is_participle(word: &str) -> bool {
+    (word.chars().count() > 3 && word.ends_with("ed")) || IRREGULAR_PARTICIPLES.contains(&word)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{analyze, AnalysisSettings, Annotation, Category};
+    use crate::Options;
+
+    fn annotated<'a>(text: &'a str, annotations: &[Annotation]) -> Vec<(Category, &'a str)> {
+        annotations
+            .iter()
+            .map(|annotation| {
+                let start = text.char_indices().nth(annotation.start).map_or(text.len(), |(index, _)| index);
+                let end = text.char_indices().nth(annotation.end).map_or(text.len(), |(index, _)| index);
+                (annotation.category, &text[start..end])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn categories() {
+        let html = "<p>Café owners utilize numerous tricks. The report was quickly written by Anna and I think it is really good.</p>";
+        let analysis = analyze(html, &Options::default(), &AnalysisSettings::default());
+
+        assert_eq!(2, analysis.sentences.len());
+        let first = &analysis.sentences[0];
+        assert_eq!(
+            vec![(Category::SimplerAlternative, "utilize"), (Category::SimplerAlternative, "numerous")],
+            annotated(&analysis.text, &first.annotations)
+        );
+        assert_eq!(Some("use".to_string()), first.annotations[0].suggestion);
+
+        assert_eq!(
+            vec![
+                (Category::Passive, "was quickly written"),
+                (Category::Adverb, "quickly"),
+                (Category::Qualifier, "I think"),
+                (Category::Adverb, "really"),
+                (Category::Qualifier, "really"),
+            ],
+            annotated(&analysis.text, &analysis.sentences[1].annotations)
+        );
+    }
+
+    #[test]
+    fn settings() {
+        let settings: AnalysisSettings = serde_json::from_str(r#"{"veryLongSentenceWords": 3, "adverbExceptions": ["quickly"], "qualifiers": []}"#).unwrap();
+        let analysis = analyze("<p>It was done quickly today.</p><p>Short one.</p>", &Options::default(), &settings);
+
+        assert_eq!(
+            vec![(Category::VeryLongSentence, "It was done quickly today."), (Category::Passive, "was done")],
+            annotated(&analysis.text, &analysis.sentences[0].annotations)
+        );
+        assert!(analysis.sentences[1].annotations.is_empty());
+        assert_eq!((5, 2), (analysis.sentences[0].words, analysis.sentences[1].words));
+        assert_eq!(analysis.text.chars().count(), analysis.sentences[1].end);
+    }
+}
@@ -0,0 +1,138 @@
+//! Command line interface to the cleanup pipeline.
+//!
+//! Reads HTML from a file or standard input, in any encoding, and writes the result to
+//! standard output: plain text for `text`, CSV for `tables --csv`, JSON for the other commands.
+
+use std::io::{self, Read, Write};
+use std::process;
+
+use readability_text_cleanup::{
+    analyze, decode_bytes, extract_images, extract_links, extract_metadata, extract_tables, keywords, prepare_document, prepare_paragraphs, prepare_text_with_options,
+    readability, summarize, AnalysisSettings, KeywordOptions, Language, Length, Options, Readability, RubyText, SummaryOptions, Table, TextStats,
+};
+
+const USAGE: &str = "usage: rtc [text|sentences|metadata|document|readability|analyze|summary|keywords|tables|images|links] [--charset LABEL] [--keep-abbreviations]
+           [--keep-citations] [--drop-boilerplate] [--drop-captions] [--math-placeholder TOKEN] [--ruby base|parenthesized|reading]
+           [--language CODE] [--sentences N|--words N|--characters N] [--csv] [--base URL] [FILE]
+
+Cleans up the HTML read from FILE, or standard input, and prints
+  text         the cleaned up text (default)
+  sentences    the paragraphs as arrays of sentences
+  metadata     title, byline, language and other document information
+  document     text, sentences with offsets, metadata and statistics
+  readability  readability scores and the counts they are based on, for the declared
+               or detected language unless --language (en, de, fr, es, it, sv) is given
+  analyze      very long sentences, passive voice, adverbs, qualifiers and words with
+               simpler alternatives, with character offsets into the cleaned text
+  summary      the most representative sentences, 3 unless --sentences, --words or
+               --characters limit the length
+  keywords     the highest scoring keyphrases with their character offsets
+  tables       the data tables with caption, header and body rows, spans expanded;
+               --csv prints them as CSV instead, separated by empty lines
+  images       the images with their best URL, alt text, caption and dimensions,
+               tracking pixels and spacers left out, the lead image flagged
+  links        the links with their text, rel keywords and whether they are in the main
+               content, URLs resolved against the page URL given by --base";
+
+fn main() {
+    let mut command = "text".to_string();
+    let mut charset = None;
+    let mut options = Options::default();
+    let mut language = None;
+    let mut summary = SummaryOptions::default();
+    let mut csv = false;
+    let mut base = None;
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            "--charset" => charset = Some(args.next().unwrap_or_else(|| fail("missing value for --charset"))),
+            "--language" => {
+                let code = args.next().unwrap_or_else(|| fail("missing value for --language"));
+                language = Some(Language::from_tag(&code).unwrap_or_else(|| fail(&format!("unsupported language {}", code))));
+            }
+            "--sentences" | "--words" | "--characters" => {
+                let value = args.next().unwrap_or_else(|| fail(&format!("missing value for {}", arg)));
+                let limit = value.parse().unwrap_or_else(|_| fail(&format!("{} expects a number, got {}", arg, value)));
+                summary.length = match arg.as_str() {
+                    "--sentences" => Length::Sentences(limit),
+                    "--words" => Length::Words(limit),
+                    _ => Length::Characters(limit),
+                };
+            }
+            "--keep-abbreviations" => options.replace_abbreviations = false,
+            "--keep-citations" => options.strip_citations = false,
+            "--drop-boilerplate" => options.drop_boilerplate = true,
+            "--drop-captions" => options.captions = false,
+            "--ruby" => {
+                let mode = args.next().unwrap_or_else(|| fail("missing value for --ruby"));
+                options.ruby = match mode.as_str() {
+                    "base" => RubyText::Base,
+                    "parenthesized" => RubyText::Parenthesized,
+                    "reading" => RubyText::Reading,
+                    _ => fail(&format!("unsupported ruby text {}", mode)),
+                };
+            }
+            "--math-placeholder" => options.math_placeholder = Some(args.next().unwrap_or_else(|| fail("missing value for --math-placeholder"))),
+            "--csv" => csv = true,
+            "--base" => base = Some(args.next().unwrap_or_else(|| fail("missing value for --base"))),
+            "text" | "sentences" | "metadata" | "document" | "readability" | "analyze" | "summary" | "keywords" | "tables" | "images" | "links" => command = arg,
+            _ if arg.starts_with('-') && arg != "-" => fail(&format!("unknown option {}", arg)),
+            _ if path.is_none() => path = Some(arg),
+            _ => fail(&format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let bytes = match path.as_deref() {
+        None | Some("-") => {
+            let mut bytes = vec![];
+            io::stdin().read_to_end(&mut bytes).map(|_| bytes)
+        }
+        Some(path) => std::fs::read(path),
+    }
+    .unwrap_or_else(|err| fail(&format!("cannot read input: {}", err)));
+    let html = decode_bytes(&bytes, charset.as_deref());
+
+    let output = match command.as_str() {
+        "text" => prepare_text_with_options(&html, &options),
+        "sentences" => to_json(&prepare_paragraphs(&html, &options)),
+        "metadata" => to_json(&extract_metadata(&html)),
+        "document" => to_json(&prepare_document(&html, &options)),
+        "analyze" => to_json(&analyze(&html, &options, &AnalysisSettings::default())),
+        "summary" => summarize(&html, &options, &summary).text,
+        "keywords" => to_json(&keywords(&html, &options, &KeywordOptions::default())),
+        "tables" if csv => {
+            let tables: Vec<String> = extract_tables(&html).iter().map(Table::to_csv).collect();
+            tables.join("\r\n").trim_end_matches("\r\n").to_string()
+        }
+        "tables" => to_json(&extract_tables(&html)),
+        "images" => to_json(&extract_images(&html)),
+        "links" => to_json(&extract_links(&html, base.as_deref())),
+        _ => match language {
+            Some(language) => {
+                let stats = TextStats::of(&prepare_paragraphs(&html, &options), language);
+                to_json(&Readability::of(stats, language))
+            }
+            None => to_json(&readability(&html, &options)),
+        },
+    };
+
+    let mut stdout = io::stdout().lock();
+    if writeln!(stdout, "{}", output).is_err() {
+        process::exit(1);
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).expect("serializable")
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
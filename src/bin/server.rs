@@ -0,0 +1,56 @@
+//! HTTP service for the cleanup pipeline, see `readability_text_cleanup::server`.
+//!
+//! Usage: server [--listen ADDR] [--max-body-bytes N] [--io-timeout SECS]
+//!               [--processing-timeout SECS] [--max-connections N]
+
+use std::net::TcpListener;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use readability_text_cleanup::server::{serve, Config};
+
+const USAGE: &str = "usage: server [--listen ADDR] [--max-body-bytes N] [--io-timeout SECS] [--processing-timeout SECS] [--max-connections N]";
+
+fn main() {
+    let mut listen = "127.0.0.1:8080".to_string();
+    let mut config = Config::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            println!("{}", USAGE);
+            return;
+        }
+        let value = args.next().unwrap_or_else(|| fail(&format!("missing value for {}", arg)));
+        match arg.as_str() {
+            "--listen" => listen = value,
+            "--max-body-bytes" => config.max_body_bytes = number(&arg, &value),
+            "--io-timeout" => config.io_timeout = Duration::from_secs(number(&arg, &value) as u64),
+            "--processing-timeout" => config.processing_timeout = Duration::from_secs(number(&arg, &value) as u64),
+            "--max-connections" => config.max_connections = number(&arg, &value),
+            _ => fail(&format!("unknown argument {}", arg)),
+        }
+    }
+
+    let listener = TcpListener::bind(&listen).unwrap_or_else(|err| fail(&format!("cannot listen on {}: {}", listen, err)));
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&shutdown);
+    ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst)).expect("signal handler");
+
+    eprintln!("listening on {}", listener.local_addr().map(|addr| addr.to_string()).unwrap_or(listen));
+    if let Err(err) = serve(listener, config, shutdown) {
+        fail(&err.to_string());
+    }
+    eprintln!("shut down");
+}
+
+fn number(arg: &str, value: &str) -> usize {
+    value.parse().unwrap_or_else(|_| fail(&format!("{} expects a number, got {}", arg, value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
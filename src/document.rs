@@ -0,0 +1,104 @@
+use serde::Serialize;
+
+use super::language::language_code;
+use super::metadata::{extract_metadata, Metadata};
+use super::stats::{ReadingRates, Stats};
+use super::Options;
+
+/// Structured result of the cleanup pipeline.
+///
+/// All offsets count Unicode characters of `text`, like the ones of `analyze` and `keywords`,
+/// so `text.chars().skip(sentence.start).take(sentence.end - sentence.start)` is the sentence text.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    /// The same text `prepare_text_with_options` returns
+    pub text: String,
+    pub paragraphs: Vec<Paragraph>,
+    pub metadata: Metadata,
+    pub stats: Stats,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paragraph {
+    pub start: usize,
+    pub end: usize,
+    pub sentences: Vec<Sentence>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sentence {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Runs the cleanup pipeline over `html` and keeps its structure:
+/// paragraphs, sentences with their offsets, document metadata and statistics
+pub fn prepare_document(html: &str, options: &Options) -> Document {
+    let segmented = super::prepare_paragraphs(html, options);
+
+    let mut text = String::new();
+    // characters of `text`
+    let mut length = 0;
+    let mut paragraphs = Vec::with_capacity(segmented.len());
+    for paragraph in &segmented {
+        if !text.is_empty() {
+            text.push_str("\n\n");
+            length += 2;
+        }
+
+        let start = length;
+        let mut sentences = Vec::with_capacity(paragraph.len());
+        for sentence in paragraph {
+            if length > start {
+                text.push(' ');
+                length += 1;
+            }
+            let end = length + sentence.chars().count();
+            sentences.push(Sentence { text: sentence.clone(), start: length, end });
+            text.push_str(sentence);
+            length = end;
+        }
+        paragraphs.push(Paragraph { start, end: length, sentences });
+    }
+
+    let metadata = extract_metadata(html);
+    let language = language_code(metadata.language.as_deref(), &segmented);
+    Document {
+        text,
+        paragraphs,
+        metadata,
+        stats: Stats::of(&segmented, &language, &ReadingRates::default()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::prepare_document;
+    use crate::Options;
+
+    #[test]
+    fn offsets_match_text() {
+        let html = "<html lang=\"fr\"><head><meta name=\"author\" content=\"Marie\"></head><p>Première phrase. Deuxième phrase!</p><p>Troisième?</p></html>";
+        let document = prepare_document(html, &Options::default());
+        let chars: Vec<char> = document.text.chars().collect();
+        let slice = |start: usize, end: usize| chars[start..end].iter().collect::<String>();
+
+        assert_eq!(crate::prepare_text(html), document.text);
+        assert_eq!(2, document.paragraphs.len());
+        for paragraph in &document.paragraphs {
+            for sentence in &paragraph.sentences {
+                assert_eq!(sentence.text, slice(sentence.start, sentence.end));
+            }
+        }
+        assert_eq!("Troisième?", slice(document.paragraphs[1].start, document.paragraphs[1].end));
+        assert_eq!(chars.len(), document.paragraphs[1].end);
+        assert_eq!(Some("fr".to_string()), document.metadata.language);
+        assert_eq!(Some("Marie".to_string()), document.metadata.byline);
+        assert_eq!(document.text.chars().count(), document.stats.characters);
+        assert_eq!(3, document.stats.sentences);
+    }
+}
@@ -0,0 +1,47 @@
+//! DOM traversal shared by the extractors. Element names come from `html2md::common::tag_name`.
+
+use html2md::common::parent;
+use html5ever::parse_document;
+use html5ever::tendril::TendrilSink;
+use markup5ever_rcdom::{Handle, RcDom};
+
+/// Parses `html` the way browsers do, malformed markup is repaired rather than rejected
+pub fn parse_dom(html: &str) -> RcDom {
+    parse_document(RcDom::default(), Default::default())
+        .from_utf8()
+        .read_from(&mut html.as_bytes())
+        .unwrap()
+}
+
+/// The node and all of its descendants, in document order
+pub fn descendants(node: &Handle) -> impl Iterator<Item = Handle> {
+    let mut stack = vec![node.clone()];
+    std::iter::from_fn(move || {
+        let node = stack.pop()?;
+        stack.extend(node.children.borrow().iter().rev().cloned());
+        Some(node)
+    })
+}
+
+/// Ancestors of the node, from its parent up to the document
+pub fn ancestors(node: &Handle) -> impl Iterator<Item = Handle> {
+    std::iter::successors(parent(node), parent)
+}
+
+#[cfg(test)]
+mod test {
+    use html2md::common::tag_name;
+
+    use super::{ancestors, descendants, parse_dom};
+
+    #[test]
+    fn traversal_order() {
+        let dom = parse_dom("<div><p>One <em>two</em></p><ul><li>three</li></ul></div>");
+        let names: Vec<String> = descendants(&dom.document).map(|node| tag_name(&node)).filter(|name| !name.is_empty()).collect();
+        assert_eq!(vec!["html", "head", "body", "div", "p", "em", "ul", "li"], names);
+
+        let em = descendants(&dom.document).find(|node| tag_name(node) == "em").unwrap();
+        let names: Vec<String> = ancestors(&em).map(|node| tag_name(&node)).collect();
+        assert_eq!(vec!["p", "div", "body", "html", ""], names);
+    }
+}
@@ -0,0 +1,289 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252, X_USER_DEFINED};
+
+/// The prescan only looks at the start of the document, as browsers do
+const PRESCAN_LIMIT: usize = 1024;
+
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b'\t' | b'\n' | b'\x0C' | b'\r' | b' ')
+}
+
+/// Determines the encoding of an HTML byte stream following the WHATWG
+/// encoding sniffing algorithm: BOM, transport layer hint, `<meta>` prescan
+/// and finally a frequency based guess.
+///
+/// `hint` is either a bare charset label (`Shift_JIS`) or a full
+/// `Content-Type` header value (`text/html; charset=Shift_JIS`).
+pub(crate) fn sniff(bytes: &[u8], hint: Option<&str>) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+
+    if let Some(encoding) = hint.and_then(encoding_from_hint) {
+        return encoding;
+    }
+
+    if let Some(encoding) = prescan(&bytes[..bytes.len().min(PRESCAN_LIMIT)]) {
+        return encoding;
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, true)
+}
+
+/// Decodes `bytes` into a string using the sniffed encoding.
+/// Malformed sequences are replaced with U+FFFD.
+pub(crate) fn decode(bytes: &[u8], hint: Option<&str>) -> String {
+    let (text, _, _) = sniff(bytes, hint).decode(bytes);
+    text.into_owned()
+}
+
+fn encoding_from_hint(hint: &str) -> Option<&'static Encoding> {
+    if hint.contains('=') {
+        extract_charset(hint.as_bytes())
+    } else {
+        Encoding::for_label(hint.trim().as_bytes())
+    }
+}
+
+/// "Prescan a byte stream to determine its encoding" from the HTML standard
+fn prescan(input: &[u8]) -> Option<&'static Encoding> {
+    let mut position = 0;
+    while position < input.len() {
+        let rest = &input[position..];
+        if rest.starts_with(b"<!--") {
+            // the dashes of the opening sequence may be shared, so `<!-->` closes the comment
+            match find(&input[position + 2..], b"-->") {
+                Some(offset) => position += 2 + offset + 3,
+                None => return None,
+            }
+            continue;
+        } else if rest.len() >= 6 && rest[..5].eq_ignore_ascii_case(b"<meta") && (is_whitespace(rest[5]) || rest[5] == b'/') {
+            position += 5;
+            if let Some(encoding) = prescan_meta(input, &mut position) {
+                return Some(encoding);
+            }
+            continue;
+        } else if rest[0] == b'<' && (is_tag_start(&rest[1..]) || (rest.get(1) == Some(&b'/') && is_tag_start(&rest[2..]))) {
+            // any other start or end tag, skip its attributes
+            while position < input.len() && !is_whitespace(input[position]) && input[position] != b'>' {
+                position += 1;
+            }
+            while get_attribute(input, &mut position).is_some() {}
+        } else if rest.starts_with(b"<!") || rest.starts_with(b"</") || rest.starts_with(b"<?") {
+            match rest.iter().position(|&b| b == b'>') {
+                Some(offset) => position += offset,
+                None => return None,
+            }
+        }
+        position += 1;
+    }
+
+    None
+}
+
+/// Processes the attributes of a `<meta>` tag found by the prescan
+fn prescan_meta(input: &[u8], position: &mut usize) -> Option<&'static Encoding> {
+    let mut seen: Vec<Vec<u8>> = vec![];
+    let mut got_pragma = false;
+    let mut need_pragma = None;
+    let mut charset = None;
+
+    while let Some((name, value)) = get_attribute(input, position) {
+        if seen.contains(&name) {
+            continue;
+        }
+
+        match name.as_slice() {
+            b"http-equiv" => got_pragma |= value == b"content-type",
+            b"content" if charset.is_none() => {
+                if let Some(encoding) = extract_charset(&value) {
+                    charset = Some(encoding);
+                    need_pragma = Some(true);
+                }
+            }
+            b"charset" => {
+                charset = Encoding::for_label(&value);
+                need_pragma = Some(false);
+            }
+            _ => {}
+        }
+        seen.push(name);
+    }
+
+    match need_pragma {
+        None => return None,
+        Some(true) if !got_pragma => return None,
+        _ => {}
+    }
+
+    charset.map(|encoding| {
+        if encoding == UTF_16BE || encoding == UTF_16LE {
+            UTF_8
+        } else if encoding == X_USER_DEFINED {
+            WINDOWS_1252
+        } else {
+            encoding
+        }
+    })
+}
+
+/// "Get an attribute" from the HTML standard. Names and values are lowercased,
+/// `None` means there are no more attributes in the current tag.
+fn get_attribute(input: &[u8], position: &mut usize) -> Option<(Vec<u8>, Vec<u8>)> {
+    while *position < input.len() && (is_whitespace(input[*position]) || input[*position] == b'/') {
+        *position += 1;
+    }
+    if *position >= input.len() || input[*position] == b'>' {
+        return None;
+    }
+
+    let mut name = vec![];
+    let mut value = vec![];
+
+    // attribute name
+    loop {
+        let b = *input.get(*position)?;
+        if b == b'=' && !name.is_empty() {
+            *position += 1;
+            break;
+        }
+        if is_whitespace(b) {
+            while *position < input.len() && is_whitespace(input[*position]) {
+                *position += 1;
+            }
+            if input.get(*position) != Some(&b'=') {
+                return Some((name, value));
+            }
+            *position += 1;
+            break;
+        }
+        if b == b'/' || b == b'>' {
+            return Some((name, value));
+        }
+        name.push(b.to_ascii_lowercase());
+        *position += 1;
+    }
+
+    // attribute value
+    while *position < input.len() && is_whitespace(input[*position]) {
+        *position += 1;
+    }
+    let b = *input.get(*position)?;
+    if b == b'"' || b == b'\'' {
+        *position += 1;
+        loop {
+            let c = *input.get(*position)?;
+            *position += 1;
+            if c == b {
+                return Some((name, value));
+            }
+            value.push(c.to_ascii_lowercase());
+        }
+    }
+    if b == b'>' {
+        return Some((name, value));
+    }
+    loop {
+        let c = *input.get(*position)?;
+        if is_whitespace(c) || c == b'>' {
+            return Some((name, value));
+        }
+        value.push(c.to_ascii_lowercase());
+        *position += 1;
+    }
+}
+
+/// "Extract a character encoding from a meta element" from the HTML standard,
+/// also used for `Content-Type` header values
+fn extract_charset(content: &[u8]) -> Option<&'static Encoding> {
+    let mut position = 0;
+    loop {
+        let offset = content[position..]
+            .windows(7)
+            .position(|window| window.eq_ignore_ascii_case(b"charset"))?;
+        position += offset + 7;
+
+        while position < content.len() && is_whitespace(content[position]) {
+            position += 1;
+        }
+        if content.get(position) != Some(&b'=') {
+            continue;
+        }
+        position += 1;
+        while position < content.len() && is_whitespace(content[position]) {
+            position += 1;
+        }
+
+        let rest = &content[position..];
+        return match rest.first() {
+            Some(&quote) if quote == b'"' || quote == b'\'' => {
+                let end = rest[1..].iter().position(|&b| b == quote)?;
+                Encoding::for_label(&rest[1..1 + end])
+            }
+            Some(_) => {
+                let end = rest.iter().position(|&b| is_whitespace(b) || b == b';').unwrap_or(rest.len());
+                Encoding::for_label(&rest[..end])
+            }
+            None => None,
+        };
+    }
+}
+
+fn is_tag_start(input: &[u8]) -> bool {
+    input.first().is_some_and(u8::is_ascii_alphabetic)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod test {
+    use encoding_rs::{GBK, ISO_8859_2, SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+    use super::{decode, sniff};
+
+    #[test]
+    fn bom_wins_over_everything() {
+        let bytes = b"\xEF\xBB\xBF<meta charset=\"windows-1252\"><p>caf\xC3\xA9</p>";
+        assert_eq!(UTF_8, sniff(bytes, Some("text/html; charset=ISO-8859-2")));
+        assert_eq!("<meta charset=\"windows-1252\"><p>café</p>", decode(bytes, None));
+    }
+
+    #[test]
+    fn transport_hint() {
+        let bytes = b"<p>\xB1</p>";
+        assert_eq!(ISO_8859_2, sniff(bytes, Some("text/html; charset=\"ISO-8859-2\"")));
+        assert_eq!(ISO_8859_2, sniff(bytes, Some("iso-8859-2")));
+        assert_eq!("<p>ą</p>", decode(bytes, Some("latin2")));
+    }
+
+    #[test]
+    fn meta_prescan() {
+        assert_eq!(SHIFT_JIS, sniff(b"<html><head><META CHARSET=shift_jis>", None));
+        assert_eq!(GBK, sniff(b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=gb2312\">", None));
+        // content without the pragma is ignored
+        assert_eq!(WINDOWS_1252, sniff(b"<meta content=\"text/html; charset=iso-8859-2\"><p>caf\xE9</p>", None));
+        // charsets in comments and attribute values are ignored
+        assert_eq!(WINDOWS_1252, sniff(b"<!-- <meta charset=gbk> --><a title='<meta charset=gbk>'>caf\xE9</a>", None));
+        // utf-16 can't be declared from within the document
+        assert_eq!(UTF_8, sniff(b"<meta charset=utf-16le>", None));
+    }
+
+    #[test]
+    fn heuristic_fallback() {
+        let (bytes, _, _) = SHIFT_JIS.encode("<p>日本語のテキストをここに書きます。これは文字化けしてはいけません。</p>");
+        assert_eq!(SHIFT_JIS, sniff(&bytes, None));
+
+        let (bytes, _, _) = WINDOWS_1252.encode("<p>Le café était déjà fermé à côté de l’église.</p>");
+        assert_eq!("<p>Le café était déjà fermé à côté de l’église.</p>", decode(&bytes, None));
+    }
+
+    #[test]
+    fn prepare_bytes() {
+        let (bytes, _, _) = WINDOWS_1252.encode("<html><head><meta charset=\"windows-1252\"></head><body><p>Crème brûlée – 5 €.</p></body></html>");
+        assert_eq!("Crème brûlée – 5 €.", crate::prepare_bytes(&bytes, None));
+    }
+}
@@ -0,0 +1,275 @@
+//! C interface of the cleanup pipeline.
+//!
+//! Every string returned by this interface is owned by the caller and must be
+//! released with `rtc_string_free`. Fallible functions return an `RtcStatus`
+//! and leave a human readable description for `rtc_last_error_message`.
+//! The header `include/readability_text_cleanup.h` is generated from this file with `make header`.
+//!
+//! Panics are only caught when they unwind: build the library with `make capi`, which uses the
+//! `bindings` profile, rather than `--release`, whose `panic = "abort"` ends the host process.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use super::{Options, RubyText};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Result code of the fallible functions
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RtcStatus {
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// The result contains a NUL character and can't be returned as a C string
+    InteriorNul = 3,
+    /// The pipeline panicked, this is a bug. Only builds with unwinding panics, such as
+    /// `make capi`, return it: release builds abort
+    Panic = 4,
+}
+
+/// Text kept for ruby annotations, such as the furigana of Japanese text
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+// only C callers construct the other variants
+#[allow(dead_code)]
+pub enum RtcRuby {
+    /// Base text only
+    Base = 0,
+    /// Base text followed by its reading in parentheses
+    Parenthesized = 1,
+    /// Reading in place of the annotated base text
+    Reading = 2,
+}
+
+/// Switches for the optional steps of the cleanup pipeline,
+/// obtain the defaults with `rtc_options_default`
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct RtcOptions {
+    /// Rewrite common abbreviations ("e.g.", "Dr.") so that their dots don't end sentences
+    pub replace_abbreviations: bool,
+    /// Remove numeric citation markers such as `[12]`
+    pub strip_citations: bool,
+    /// Leave out the content of `nav`, `aside`, `form` and `footer` elements, mostly site navigation and chrome
+    pub drop_boilerplate: bool,
+    /// Keep the captions of figures and tables, as paragraphs of their own
+    pub captions: bool,
+    /// NULL, or a NUL-terminated UTF-8 token written in place of every formula, such as `[MATH]`.
+    /// Formulas are written as linear text when NULL
+    pub math_placeholder: *const c_char,
+    /// Text kept for ruby annotations
+    pub ruby: RtcRuby,
+}
+
+/// Options used by `prepare_text`
+#[no_mangle]
+pub extern "C" fn rtc_options_default() -> RtcOptions {
+    let options = Options::default();
+    RtcOptions {
+        replace_abbreviations: options.replace_abbreviations,
+        strip_citations: options.strip_citations,
+        drop_boilerplate: options.drop_boilerplate,
+        captions: options.captions,
+        math_placeholder: ptr::null(),
+        ruby: RtcRuby::Base,
+    }
+}
+
+/// Cleans up a NUL-terminated UTF-8 HTML document.
+///
+/// On success `*out` receives the cleaned text, free it with `rtc_string_free`.
+/// On failure `*out` is set to NULL. `options` may be NULL to use the defaults.
+///
+/// # Safety
+/// `html` must be NULL or point to a NUL-terminated string, `options` must be NULL
+/// or point to a valid `RtcOptions`, whose `math_placeholder` is NULL or a NUL-terminated
+/// string, and `out` must be NULL or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rtc_prepare_text(html: *const c_char, options: *const RtcOptions, out: *mut *mut c_char) -> RtcStatus {
+    run(out, || {
+        if html.is_null() {
+            return Err((RtcStatus::NullArgument, "`html` is NULL".to_string()));
+        }
+        let html = CStr::from_ptr(html)
+            .to_str()
+            .map_err(|err| (RtcStatus::InvalidUtf8, format!("`html` is not valid UTF-8: {}", err)))?;
+        Ok(super::prepare_text_with_options(html, &to_options(options)?))
+    })
+}
+
+/// Cleans up an HTML document given as `len` raw bytes in any encoding.
+///
+/// `hint` is NULL, a charset label or the `Content-Type` header the document was
+/// served with. Ownership rules are the same as for `rtc_prepare_text`.
+///
+/// # Safety
+/// `bytes` must point to at least `len` readable bytes, `hint` must be NULL or point
+/// to a NUL-terminated string, `options` and `out` as in `rtc_prepare_text`.
+#[no_mangle]
+pub unsafe extern "C" fn rtc_prepare_bytes(bytes: *const u8, len: usize, hint: *const c_char, options: *const RtcOptions, out: *mut *mut c_char) -> RtcStatus {
+    run(out, || {
+        if bytes.is_null() {
+            return Err((RtcStatus::NullArgument, "`bytes` is NULL".to_string()));
+        }
+        let bytes = std::slice::from_raw_parts(bytes, len);
+        let hint = if hint.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(hint)
+                .to_str()
+                .map_err(|err| (RtcStatus::InvalidUtf8, format!("`hint` is not valid UTF-8: {}", err)))?)
+        };
+        Ok(super::prepare_bytes_with_options(bytes, hint, &to_options(options)?))
+    })
+}
+
+/// Releases a string returned by this library. Passing NULL is a no-op.
+///
+/// # Safety
+/// `string` must be NULL or a pointer obtained from this library that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rtc_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Describes the last failure on the calling thread, NULL if the last call succeeded.
+///
+/// The returned string is owned by the library and stays valid until the next call
+/// into this library from the same thread.
+#[no_mangle]
+pub extern "C" fn rtc_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+unsafe fn to_options(options: *const RtcOptions) -> Result<Options, (RtcStatus, String)> {
+    let options = match options.as_ref() {
+        Some(options) => options,
+        None => return Ok(Options::default()),
+    };
+    let math_placeholder = if options.math_placeholder.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(options.math_placeholder)
+            .to_str()
+            .map_err(|err| (RtcStatus::InvalidUtf8, format!("`math_placeholder` is not valid UTF-8: {}", err)))?
+            .to_string())
+    };
+    Ok(Options {
+        replace_abbreviations: options.replace_abbreviations,
+        strip_citations: options.strip_citations,
+        drop_boilerplate: options.drop_boilerplate,
+        captions: options.captions,
+        math_placeholder,
+        ruby: match options.ruby {
+            RtcRuby::Base => RubyText::Base,
+            RtcRuby::Parenthesized => RubyText::Parenthesized,
+            RtcRuby::Reading => RubyText::Reading,
+        },
+    })
+}
+
+fn set_last_error(message: Option<String>) {
+    // messages are built from our own strings, strip NULs just in case
+    let message = message.map(|message| CString::new(message.replace('\0', "")).unwrap_or_default());
+    LAST_ERROR.with(|error| *error.borrow_mut() = message);
+}
+
+/// Runs `job` guarding against panics and hands its result to the caller through `out`.
+/// With `panic = "abort"` there is nothing to catch and the process ends instead
+unsafe fn run<F>(out: *mut *mut c_char, job: F) -> RtcStatus
+where F: FnOnce() -> Result<String, (RtcStatus, String)> {
+    if out.is_null() {
+        set_last_error(Some("`out` is NULL".to_string()));
+        return RtcStatus::NullArgument;
+    }
+    *out = ptr::null_mut();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(job)).unwrap_or_else(|payload| {
+        let reason = payload.downcast_ref::<&str>().map(|reason| reason.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err((RtcStatus::Panic, format!("cleanup panicked: {}", reason)))
+    });
+    let result = result.and_then(|text| {
+        CString::new(text).map_err(|err| (RtcStatus::InteriorNul, format!("result contains NUL at byte {}", err.nul_position())))
+    });
+
+    match result {
+        Ok(text) => {
+            set_last_error(None);
+            *out = text.into_raw();
+            RtcStatus::Ok
+        }
+        Err((status, message)) => {
+            set_last_error(Some(message));
+            status
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+
+    use super::*;
+
+    #[test]
+    fn prepare_text_roundtrip() {
+        let html = CString::new("<p>First sentence. Second one, e.g. this.</p><p>See here[1].</p>").unwrap();
+        let mut out = ptr::null_mut();
+        unsafe {
+            assert_eq!(RtcStatus::Ok, rtc_prepare_text(html.as_ptr(), ptr::null(), &mut out));
+            assert_eq!("First sentence. Second one, eg this.\n\nSee here.", CStr::from_ptr(out).to_str().unwrap());
+            assert!(rtc_last_error_message().is_null());
+            rtc_string_free(out);
+        }
+
+        let options = RtcOptions { strip_citations: false, ..rtc_options_default() };
+        unsafe {
+            assert_eq!(RtcStatus::Ok, rtc_prepare_text(html.as_ptr(), &options, &mut out));
+            assert_eq!("First sentence. Second one, eg this.\n\nSee here[1].", CStr::from_ptr(out).to_str().unwrap());
+            rtc_string_free(out);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut out = ptr::null_mut();
+        unsafe {
+            assert_eq!(RtcStatus::NullArgument, rtc_prepare_text(ptr::null(), ptr::null(), &mut out));
+            assert!(out.is_null());
+            assert_eq!("`html` is NULL", CStr::from_ptr(rtc_last_error_message()).to_str().unwrap());
+
+            let invalid = b"<p>\xFF</p>\0";
+            assert_eq!(RtcStatus::InvalidUtf8, rtc_prepare_text(invalid.as_ptr() as *const c_char, ptr::null(), &mut out));
+            assert!(out.is_null());
+
+            let html = CString::new("<p>x</p>").unwrap();
+            assert_eq!(RtcStatus::NullArgument, rtc_prepare_text(html.as_ptr(), ptr::null(), ptr::null_mut()));
+        }
+    }
+
+    #[test]
+    fn prepare_bytes() {
+        let bytes = b"<p>Caf\xE9 cr\xE8me.</p>";
+        let hint = CString::new("text/html; charset=windows-1252").unwrap();
+        let mut out = ptr::null_mut();
+        unsafe {
+            assert_eq!(RtcStatus::Ok, rtc_prepare_bytes(bytes.as_ptr(), bytes.len(), hint.as_ptr(), ptr::null(), &mut out));
+            assert_eq!("Café crème.", CStr::from_ptr(out).to_str().unwrap());
+            rtc_string_free(out);
+        }
+    }
+}
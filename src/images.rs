@@ -0,0 +1,123 @@
+use html2md::common::{get_tag_attr, tag_name, text_content};
+use html2md::images::{best_source, dimension, is_spacer};
+use markup5ever_rcdom::Handle;
+use serde::Serialize;
+
+use super::dom::{ancestors, descendants, parse_dom};
+
+/// Images smaller than this in either dimension are icons or thumbnails, never the lead image
+const LEAD_MIN_SIZE: u32 = 100;
+
+/// Content image found in the HTML markup
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Image {
+    /// Best URL of the image: the largest `srcset` candidate of the image and its `picture`
+    /// sources, then the `data-src` and `data-lazy-src` of lazy loading scripts, then `src`
+    pub url: String,
+    pub alt: Option<String>,
+    /// Caption of the enclosing `figure`
+    pub caption: Option<String>,
+    /// Width in pixels, from the attribute or the inline style
+    pub width: Option<u32>,
+    /// Height in pixels, from the attribute or the inline style
+    pub height: Option<u32>,
+    /// Whether this is the lead image of the page, the one shown in article cards
+    pub lead: bool,
+}
+
+/// Extracts the images of `html` in document order, each URL once. Tracking pixels, spacer GIFs
+/// and hidden images are skipped. The lead image is the first one outside of the page header,
+/// navigation, sidebars and footer that isn't a logo, an icon or an avatar and isn't too small
+pub fn extract_images(html: &str) -> Vec<Image> {
+    let dom = parse_dom(html);
+
+    let mut images: Vec<Image> = vec![];
+    let mut lead_found = false;
+    for img in descendants(&dom.document).filter(|node| tag_name(node) == "img") {
+        let url = match best_source(&img) {
+            Some(url) if !url.is_empty() && !is_spacer(&img) => url,
+            _ => continue,
+        };
+        if images.iter().any(|image| image.url == url) {
+            continue;
+        }
+
+        let mut image = Image {
+            alt: get_tag_attr(&img, "alt").map(|alt| alt.split_whitespace().collect::<Vec<&str>>().join(" ")).filter(|alt| !alt.is_empty()),
+            caption: ancestors(&img).find(|node| tag_name(node) == "figure").and_then(|figure| caption(&figure)),
+            width: dimension(&img, "width"),
+            height: dimension(&img, "height"),
+            url,
+            lead: false,
+        };
+        if !lead_found && is_lead_candidate(&img, &image) {
+            image.lead = true;
+            lead_found = true;
+        }
+        images.push(image);
+    }
+    images
+}
+
+fn is_lead_candidate(img: &Handle, image: &Image) -> bool {
+    let in_chrome = ancestors(img).any(|node| matches!(tag_name(&node).as_str(), "header" | "nav" | "aside" | "footer"));
+    let small = image.width.into_iter().chain(image.height).any(|length| length < LEAD_MIN_SIZE);
+    let hints = format!("{} {}", image.url, get_tag_attr(img, "class").unwrap_or_default()).to_lowercase();
+    let decoration = ["logo", "icon", "avatar", "sprite", "badge"].iter().any(|word| hints.contains(word));
+    !in_chrome && !small && !decoration
+}
+
+/// Collapsed text of the first `figcaption` of the figure
+fn caption(figure: &Handle) -> Option<String> {
+    let children = figure.children.borrow();
+    let figcaption = children.iter().find(|child| tag_name(child) == "figcaption")?;
+    Some(text_content(figcaption, &["script", "style"], true)).filter(|caption| !caption.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{extract_images, Image};
+
+    #[test]
+    fn lazy_loading_and_captions() {
+        let images = extract_images(
+            r#"<header><img src="/logo.svg" alt="Daily News"></header>
+            <article>
+                <figure>
+                    <picture>
+                        <source srcset="/photo-800.webp 800w, /photo-1600.webp 1600w">
+                        <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" data-src="/photo.jpg" alt=" Harbour at  dawn " width="800" height="450">
+                    </picture>
+                    <figcaption>The harbour, <em>early morning</em>.</figcaption>
+                </figure>
+                <img class="lazy" data-lazy-src="/chart.png" style="width: 640px">
+            </article>"#,
+        );
+        assert_eq!(
+            vec![
+                Image { url: "/logo.svg".to_string(), alt: Some("Daily News".to_string()), ..Image::default() },
+                Image {
+                    url: "/photo-1600.webp".to_string(),
+                    alt: Some("Harbour at dawn".to_string()),
+                    caption: Some("The harbour, early morning.".to_string()),
+                    width: Some(800),
+                    height: Some(450),
+                    lead: true,
+                },
+                Image { url: "/chart.png".to_string(), width: Some(640), ..Image::default() },
+            ],
+            images
+        );
+    }
+
+    #[test]
+    fn tracking_pixels_and_duplicates() {
+        let images = extract_images(
+            r#"<p><img src="/spacer.gif"><img src="https://stats.example.com/p?id=1" width="1" height="1">
+            <img src="/icons/share.png" width="24" height="24"><img src="/photo.jpg"><img src="/photo.jpg"></p>"#,
+        );
+        let urls: Vec<(&str, bool)> = images.iter().map(|image| (image.url.as_str(), image.lead)).collect();
+        assert_eq!(vec![("/icons/share.png", false), ("/photo.jpg", true)], urls);
+    }
+}
@@ -1,6 +1,4 @@
-use regex::{Captures, Regex};
-
-use lazy_static::lazy_static;
+use regex::Regex;
 
 fn remove_composite_abbreviations(text: &str) -> String {
     Regex::new(r"(?P<comp>et al\.)(?:\.)")
@@ -148,11 +146,11 @@ fn repair_sentences(paragraphs: Vec<Vec<String>>) -> Vec<Vec<String>> {
                 regex.replace_all(
                     &acc,
                     match regex as *const Regex {
-                        x if x == &quote_repair_regexes[0] as *const Regex => r#"'$p""#,
-                        x if x == &quote_repair_regexes[1] as *const Regex => r#"'$p”"#,
-                        x if x == &quote_repair_regexes[2] as *const Regex => r#"$p”"#,
-                        x if x == &quote_repair_regexes[3] as *const Regex => r#"$p""#,
-                        x if x == &quote_repair_regexes[4] as *const Regex => r#"$p'"#,
+                        x if std::ptr::eq(x, &quote_repair_regexes[0]) => r#"'$p""#,
+                        x if std::ptr::eq(x, &quote_repair_regexes[1]) => r#"'$p”"#,
+                        x if std::ptr::eq(x, &quote_repair_regexes[2]) => r#"$p”"#,
+                        x if std::ptr::eq(x, &quote_repair_regexes[3]) => r#"$p""#,
+                        x if std::ptr::eq(x, &quote_repair_regexes[4]) => r#"$p'"#,
                         _ => r#"$p""#,
                     },
                 ).to_string()
@@ -163,7 +161,7 @@ fn repair_sentences(paragraphs: Vec<Vec<String>>) -> Vec<Vec<String>> {
     repaired_paragraphs
 }
 
-pub fn cut(origin_text: &String) -> Vec<Vec<String>> {
+pub fn cut(origin_text: &str) -> Vec<Vec<String>> {
     let mut text = remove_composite_abbreviations(origin_text);
     text = remove_suspension_points(&text);
     text = remove_floating_point_numbers(&text);
@@ -180,10 +178,6 @@ pub fn cut(origin_text: &String) -> Vec<Vec<String>> {
 
 #[cfg(test)]
 mod test {
-    extern crate test;
-
-    use self::test::Bencher;
-
     #[test]
     fn it_works() {
         let text = String::from("For years, people in the U.A.E.R. have accepted murky air, tainted waters and scarred landscapes as the unavoidable price of the country’s meteoric economic growth. But public dissent over environmental issues has been growing steadily in the communist nation, and now seems to be building the foundations of a fledgling green movement! In July alone, two separate demonstrations made international news when they turned violent after about 1.5 minutes... These recent successes come after a slew of ever-larger and more violent green protests over the past few years, as the environmentalist Dr. C. Jeung of China’s growth becomes harder to ignore.Some ask: “Are demonstrations are evidence of the public anger and frustration at opaque environmental management and decision-making?” Others yet say: \"Should we be scared about these 'protests'?\" The man made a quick calculation and found the result to be .625. (This is another sentence in parens.) This is the last sentence.");
@@ -198,8 +192,16 @@ mod test {
             "(This is another sentence in parens.)",
             "This is the last sentence.",
         ];
-        assert_eq!(result, super::cut(&text));
+        assert_eq!(vec![result], super::cut(&text));
     }
+}
+
+// the `test` crate is nightly-only, run with `cargo +nightly bench --features unstable`
+#[cfg(all(test, feature = "unstable"))]
+mod bench {
+    extern crate test;
+
+    use self::test::Bencher;
 
     #[bench]
     fn bench_cut_short(b: &mut Bencher) {
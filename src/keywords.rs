@@ -0,0 +1,324 @@
+//! Keyphrases of the cleaned text, scored with RAKE (Rose et al., 2010) or the statistical
+//! features of YAKE! (Campos et al., 2020).
+//!
+//! Candidates are runs of words within a sentence that don't cross punctuation and don't start
+//! or end with a stopword. Offsets count Unicode characters of the cleaned text, like the ones
+//! of `analyze`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::analysis::tokens;
+use super::language::document_language;
+use super::{Language, Options};
+
+/// Scoring of the candidate phrases
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeywordAlgorithm {
+    /// Word degree over frequency in the candidate phrases
+    #[default]
+    Rake,
+    /// Casing, position, frequency, context and spread of the words
+    Yake,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct KeywordOptions {
+    pub algorithm: KeywordAlgorithm,
+    /// Maximum number of keyphrases returned
+    pub limit: usize,
+    /// Longest keyphrase in words
+    pub max_words: usize,
+    /// Weight of the boost given to phrases sharing words with the title and headings, 0 disables it
+    pub heading_boost: f64,
+}
+
+impl Default for KeywordOptions {
+    fn default() -> Self {
+        KeywordOptions {
+            algorithm: KeywordAlgorithm::Rake,
+            limit: 10,
+            max_words: 3,
+            heading_boost: 0.5,
+        }
+    }
+}
+
+/// Character offsets of a keyphrase in the cleaned text
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Occurrence {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Keyword {
+    /// Lowercased words of the phrase separated by spaces
+    pub phrase: String,
+    /// Relevance, higher is better. YAKE! scores are inverted to follow the same order
+    pub score: f64,
+    pub occurrences: Vec<Occurrence>,
+}
+
+/// Word of the cleaned text with its offsets in the whole text
+struct Word {
+    word: String,
+    start: usize,
+    end: usize,
+    /// Stopwords and numbers, they delimit candidates
+    stopword: bool,
+    /// Capitalized other than at the start of a sentence
+    capitalized: bool,
+    acronym: bool,
+}
+
+/// A candidate phrase and where it occurs
+#[derive(Default)]
+struct Candidate {
+    words: Vec<String>,
+    occurrences: Vec<Occurrence>,
+}
+
+/// Cleans up `html` and extracts its highest scoring keyphrases, ties are broken by first occurrence
+pub fn keywords(html: &str, options: &Options, settings: &KeywordOptions) -> Vec<Keyword> {
+    let document = super::prepare_document(html, options);
+    let paragraphs: Vec<Vec<String>> = document
+        .paragraphs
+        .iter()
+        .map(|paragraph| paragraph.sentences.iter().map(|sentence| sentence.text.clone()).collect())
+        .collect();
+    let language = document_language(html, &paragraphs);
+    let title = document.metadata.title.clone().unwrap_or_default();
+
+    // sentences made of fragments, the runs of words not separated by punctuation
+    let mut sentences: Vec<Vec<Vec<Word>>> = vec![];
+    for sentence in document.paragraphs.iter().flat_map(|paragraph| &paragraph.sentences) {
+        // html2md keeps the `<title>`, the heading boost already accounts for it
+        if sentence.text.trim() == title {
+            continue;
+        }
+
+        let chars: Vec<char> = sentence.text.chars().collect();
+        let mut fragments: Vec<Vec<Word>> = vec![];
+        let mut previous_end = None;
+        for (index, token) in tokens(&sentence.text).into_iter().enumerate() {
+            let surface = &chars[token.start..token.end];
+            let word = Word {
+                stopword: language.is_stopword(&token.word) || token.word.chars().all(|ch| ch.is_numeric()),
+                capitalized: index > 0 && surface[0].is_uppercase(),
+                acronym: surface.len() > 1 && surface.iter().all(|ch| !ch.is_alphabetic() || ch.is_uppercase()),
+                word: token.word,
+                start: sentence.start + token.start,
+                end: sentence.start + token.end,
+            };
+            let separated = !previous_end.is_some_and(|end| chars[end..token.start].iter().all(|ch| ch.is_whitespace()));
+            previous_end = Some(token.end);
+            match fragments.last_mut() {
+                Some(fragment) if !separated => fragment.push(word),
+                _ => fragments.push(vec![word]),
+            }
+        }
+        sentences.push(fragments);
+    }
+
+    let candidates = candidates(&sentences, settings);
+    let heading_words: BTreeSet<String> = std::iter::once(title.as_str())
+        .chain(document.metadata.headings.iter().map(|heading| heading.text.as_str()))
+        .flat_map(|text| content_words(text, language))
+        .collect();
+    let boost = |candidate: &Candidate| {
+        let words: Vec<&String> = candidate.words.iter().filter(|word| !language.is_stopword(word)).collect();
+        let shared = words.iter().filter(|word| heading_words.contains(**word)).count();
+        1.0 + settings.heading_boost * shared as f64 / words.len().max(1) as f64
+    };
+
+    let mut keywords: Vec<Keyword> = match settings.algorithm {
+        KeywordAlgorithm::Rake => {
+            let word_scores = rake_word_scores(&candidates);
+            candidates
+                .into_iter()
+                .map(|(phrase, candidate)| {
+                    let score: f64 = candidate.words.iter().filter_map(|word| word_scores.get(word)).sum();
+                    Keyword { phrase, score: score * boost(&candidate), occurrences: candidate.occurrences }
+                })
+                .collect()
+        }
+        KeywordAlgorithm::Yake => {
+            let word_scores = yake_word_scores(&sentences);
+            candidates
+                .into_iter()
+                .map(|(phrase, candidate)| {
+                    let scores: Vec<f64> = candidate.words.iter().filter_map(|word| word_scores.get(word).copied()).collect();
+                    let score = scores.iter().product::<f64>()
+                        / (candidate.occurrences.len() as f64 * (1.0 + scores.iter().sum::<f64>()))
+                        / boost(&candidate);
+                    Keyword { phrase, score: 1.0 / score, occurrences: candidate.occurrences }
+                })
+                .collect()
+        }
+    };
+
+    keywords.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.occurrences[0].start.cmp(&b.occurrences[0].start)));
+    keywords.truncate(settings.limit);
+    keywords
+}
+
+/// Lowercased words of `text` that aren't stopwords
+fn content_words(text: &str, language: Language) -> Vec<String> {
+    tokens(text).into_iter().map(|token| token.word).filter(|word| !language.is_stopword(word)).collect()
+}
+
+/// RAKE splits fragments at every stopword, YAKE! takes all word n-grams with stopwords only inside
+fn candidates(sentences: &[Vec<Vec<Word>>], settings: &KeywordOptions) -> BTreeMap<String, Candidate> {
+    let mut candidates: BTreeMap<String, Candidate> = BTreeMap::new();
+    let mut add = |words: &[Word]| {
+        if words.is_empty() || words.len() > settings.max_words || words[0].stopword || words[words.len() - 1].stopword {
+            return;
+        }
+        let phrase: Vec<String> = words.iter().map(|word| word.word.clone()).collect();
+        let candidate = candidates.entry(phrase.join(" ")).or_default();
+        candidate.words = phrase;
+        candidate.occurrences.push(Occurrence { start: words[0].start, end: words[words.len() - 1].end });
+    };
+
+    for fragment in sentences.iter().flatten() {
+        match settings.algorithm {
+            KeywordAlgorithm::Rake => fragment.split(|word| word.stopword).for_each(&mut add),
+            KeywordAlgorithm::Yake => {
+                for start in 0..fragment.len() {
+                    for end in start + 1..=fragment.len().min(start + settings.max_words) {
+                        add(&fragment[start..end]);
+                    }
+                }
+            }
+        }
+    }
+    candidates
+}
+
+/// Degree over frequency of every word of the candidates
+fn rake_word_scores(candidates: &BTreeMap<String, Candidate>) -> BTreeMap<String, f64> {
+    let mut frequency: BTreeMap<&String, f64> = BTreeMap::new();
+    let mut degree: BTreeMap<&String, f64> = BTreeMap::new();
+    for candidate in candidates.values() {
+        let occurrences = candidate.occurrences.len() as f64;
+        for word in &candidate.words {
+            *frequency.entry(word).or_default() += occurrences;
+            *degree.entry(word).or_default() += occurrences * candidate.words.len() as f64;
+        }
+    }
+    frequency.into_iter().map(|(word, frequency)| (word.clone(), degree[word] / frequency)).collect()
+}
+
+#[derive(Default)]
+struct WordFeatures {
+    frequency: usize,
+    capitalized: usize,
+    acronym: usize,
+    sentences: Vec<usize>,
+    left: Vec<String>,
+    right: Vec<String>,
+}
+
+/// Scores of the words that aren't stopwords, lower is more relevant
+fn yake_word_scores(sentences: &[Vec<Vec<Word>>]) -> BTreeMap<String, f64> {
+    const WINDOW: usize = 2;
+
+    let mut features: BTreeMap<&str, WordFeatures> = BTreeMap::new();
+    for (index, fragment) in sentences.iter().enumerate().flat_map(|(index, fragments)| fragments.iter().map(move |fragment| (index, fragment))) {
+        for (position, word) in fragment.iter().enumerate().filter(|(_, word)| !word.stopword) {
+            let entry = features.entry(&word.word).or_default();
+            entry.frequency += 1;
+            entry.capitalized += usize::from(word.capitalized);
+            entry.acronym += usize::from(word.acronym);
+            entry.sentences.push(index);
+            let context = |words: &mut dyn Iterator<Item = &Word>| -> Vec<String> {
+                words.filter(|other| !other.stopword).map(|other| other.word.clone()).collect()
+            };
+            entry.left.extend(context(&mut fragment[position.saturating_sub(WINDOW)..position].iter()));
+            entry.right.extend(context(&mut fragment[position + 1..fragment.len().min(position + 1 + WINDOW)].iter()));
+        }
+    }
+    if features.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let frequencies: Vec<f64> = features.values().map(|features| features.frequency as f64).collect();
+    let mean = frequencies.iter().sum::<f64>() / frequencies.len() as f64;
+    let deviation = (frequencies.iter().map(|frequency| (frequency - mean).powi(2)).sum::<f64>() / frequencies.len() as f64).sqrt();
+    let max = frequencies.iter().copied().fold(0.0, f64::max);
+    let dispersion = |context: &[String]| {
+        if context.is_empty() {
+            0.0
+        } else {
+            context.iter().collect::<BTreeSet<_>>().len() as f64 / context.len() as f64
+        }
+    };
+
+    features
+        .into_iter()
+        .map(|(word, features)| {
+            let frequency = features.frequency as f64;
+            let case = features.capitalized.max(features.acronym) as f64 / (1.0 + frequency.ln());
+            let median = features.sentences[features.sentences.len() / 2] as f64;
+            let position = (3.0 + median).ln().ln();
+            let normalized_frequency = frequency / (mean + deviation);
+            let relatedness = 1.0 + (dispersion(&features.left) + dispersion(&features.right)) * frequency / max;
+            let spread = features.sentences.iter().collect::<BTreeSet<_>>().len() as f64 / sentences.len() as f64;
+            let score = relatedness * position / (case + normalized_frequency / relatedness + spread / relatedness);
+            (word.to_string(), score)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{keywords, KeywordAlgorithm, KeywordOptions};
+    use crate::Options;
+
+    const ARTICLE: &str = "<html><body><h1>Rooftop solar panels</h1>
+        <p>Solar panels are getting cheaper every year. Most homes now have rooftop solar panels.</p>
+        <p>The weather was mild. Installers say that rooftop solar panels will become standard.</p>
+        </body></html>";
+
+    #[test]
+    fn ranked_phrases_with_offsets() {
+        let text = crate::prepare_text(ARTICLE);
+        for algorithm in [KeywordAlgorithm::Rake, KeywordAlgorithm::Yake] {
+            let settings = KeywordOptions { algorithm, ..KeywordOptions::default() };
+            let keywords = keywords(ARTICLE, &Options::default(), &settings);
+
+            assert!(keywords.len() <= 10);
+            assert!(keywords.windows(2).all(|pair| pair[0].score >= pair[1].score), "{:?}", algorithm);
+            let phrases: Vec<&str> = keywords.iter().map(|keyword| keyword.phrase.as_str()).collect();
+            assert!(phrases[..3].contains(&"rooftop solar panels"), "{:?}: {:?}", algorithm, phrases);
+            assert!(phrases.iter().all(|phrase| !phrase.starts_with("the ") && !phrase.ends_with(" the")), "{:?}", phrases);
+
+            for keyword in &keywords {
+                for occurrence in &keyword.occurrences {
+                    let found: String = text.chars().skip(occurrence.start).take(occurrence.end - occurrence.start).collect();
+                    assert_eq!(keyword.phrase, found.to_lowercase());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn options() {
+        let html = "<h2>Coffee</h2><p>Green tea is popular. Strong coffee is popular. Green tea is cheap. Strong coffee is cheap.</p>";
+        let phrases = |settings: &KeywordOptions| -> Vec<String> {
+            keywords(html, &Options::default(), settings).into_iter().map(|keyword| keyword.phrase).collect()
+        };
+
+        let settings: KeywordOptions = serde_json::from_str(r#"{"limit": 1, "headingBoost": 0}"#).unwrap();
+        assert_eq!(vec!["green tea"], phrases(&settings));
+        assert_eq!(vec!["strong coffee"], phrases(&KeywordOptions { heading_boost: 1.0, ..settings.clone() }));
+        assert!(phrases(&KeywordOptions { max_words: 1, limit: 10, ..settings }).iter().all(|phrase| !phrase.contains(' ')));
+        assert!(keywords("", &Options::default(), &KeywordOptions::default()).is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use super::stats::is_cjk;
+
+lazy_static! {
+    /// Words carrying little meaning on their own, by language, including the function words
+    static ref STOPWORDS: HashMap<Language, HashSet<&'static str>> = Language::ALL
+        .iter()
+        .map(|&language| {
+            let list = match language {
+                Language::English => include_str!("language/stopwords/en.txt"),
+                Language::German => include_str!("language/stopwords/de.txt"),
+                Language::French => include_str!("language/stopwords/fr.txt"),
+                Language::Spanish => include_str!("language/stopwords/es.txt"),
+                Language::Italian => include_str!("language/stopwords/it.txt"),
+                Language::Swedish => include_str!("language/stopwords/sv.txt"),
+            };
+            (language, list.split_whitespace().chain(language.function_words().iter().copied()).collect())
+        })
+        .collect();
+}
+
+/// Languages with dedicated text analysis: syllable counting and readability formulas
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "it")]
+    Italian,
+    #[serde(rename = "sv")]
+    Swedish,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+        Language::Swedish,
+    ];
+
+    /// ISO 639-1 code
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+            Language::Swedish => "sv",
+        }
+    }
+
+    /// Language of a tag such as `de`, `fr-CA` or `en_US`, `None` for other languages
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        Language::ALL.into_iter().find(|language| language.code() == primary)
+    }
+
+    /// Guesses the language of `text` from its most frequent function words
+    pub fn detect(text: &str) -> Option<Language> {
+        let mut hits = [0usize; Language::ALL.len()];
+        for word in text.split(|ch: char| !ch.is_alphabetic()).filter(|word| !word.is_empty()) {
+            let word = word.to_lowercase();
+            for (index, language) in Language::ALL.iter().enumerate() {
+                if language.function_words().contains(&word.as_str()) {
+                    hits[index] += 1;
+                }
+            }
+        }
+
+        let best = (0..hits.len()).max_by_key(|&index| hits[index])?;
+        let ambiguous = hits.iter().enumerate().any(|(index, &count)| index != best && count == hits[best]);
+        if hits[best] < 2 || ambiguous {
+            return None;
+        }
+        Some(Language::ALL[best])
+    }
+
+    /// Whether `word`, in lowercase, is one of the most frequent function words of the language
+    pub(crate) fn is_function_word(self, word: &str) -> bool {
+        self.function_words().contains(&word)
+    }
+
+    /// Whether `word`, in lowercase, is a stopword of the language
+    pub(crate) fn is_stopword(self, word: &str) -> bool {
+        STOPWORDS[&self].contains(word.replace('’', "'").as_str())
+    }
+
+    /// Very frequent words telling the languages apart
+    fn function_words(self) -> &'static [&'static str] {
+        match self {
+            Language::English => &[
+                "the", "and", "of", "to", "is", "that", "it", "with", "for", "was", "this", "are", "be", "have", "from", "which", "by",
+                "not", "they", "you", "he", "she", "we", "at",
+            ],
+            Language::German => &[
+                "der", "die", "das", "und", "ist", "nicht", "mit", "sich", "des", "auf", "für", "dem", "den", "ein", "eine", "auch", "es",
+                "von", "zu", "wir", "ich", "sie", "wird", "sind",
+            ],
+            Language::French => &[
+                "le", "la", "les", "et", "est", "une", "des", "du", "que", "qui", "dans", "pour", "pas", "sur", "au", "avec", "il", "elle",
+                "nous", "vous", "ce", "sont", "mais", "aux",
+            ],
+            Language::Spanish => &[
+                "el", "los", "las", "y", "es", "una", "del", "que", "en", "por", "para", "con", "no", "se", "su", "al", "lo", "como",
+                "pero", "muy", "está", "son", "fue", "este",
+            ],
+            Language::Italian => &[
+                "il", "lo", "gli", "e", "è", "una", "della", "che", "di", "per", "con", "non", "sono", "nel", "alla", "anche", "come",
+                "del", "questo", "ma", "più", "ha", "dei", "delle",
+            ],
+            Language::Swedish => &[
+                "och", "att", "det", "som", "är", "en", "ett", "på", "för", "med", "av", "inte", "till", "den", "har", "jag", "var",
+                "om", "de", "men", "så", "han", "hon", "vi",
+            ],
+        }
+    }
+}
+
+/// Language of a document: its declared language if supported, otherwise the language
+/// detected in the cleaned up `paragraphs`, English if neither is conclusive
+pub(crate) fn document_language(html: &str, paragraphs: &[Vec<String>]) -> Language {
+    super::extract_metadata(html)
+        .language
+        .as_deref()
+        .and_then(Language::from_tag)
+        .or_else(|| Language::detect(&paragraphs.concat().join(" ")))
+        .unwrap_or_default()
+}
+
+/// Primary language subtag of a document: the `declared` one, otherwise the language detected
+/// in `paragraphs`, `ja` or `zh` for Japanese or Chinese script and `en` if nothing matches
+pub(crate) fn language_code(declared: Option<&str>, paragraphs: &[Vec<String>]) -> String {
+    let declared = declared
+        .and_then(|tag| tag.trim().split(['-', '_']).next())
+        .filter(|primary| !primary.is_empty());
+    if let Some(primary) = declared {
+        return primary.to_ascii_lowercase();
+    }
+
+    let text = paragraphs.concat().join(" ");
+    if let Some(language) = Language::detect(&text) {
+        return language.code().to_string();
+    }
+    let letters = text.chars().filter(|ch| ch.is_alphabetic()).count();
+    let cjk = text.chars().filter(|&ch| is_cjk(ch)).count();
+    let code = if text.chars().any(|ch| ('\u{3040}'..='\u{30FF}').contains(&ch)) {
+        "ja"
+    } else if cjk > 0 && cjk * 2 >= letters {
+        "zh"
+    } else {
+        "en"
+    };
+    code.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{document_language, language_code, Language};
+
+    #[test]
+    fn tags_and_detection() {
+        assert_eq!(Some(Language::German), Language::from_tag("de-AT"));
+        assert_eq!(Some(Language::English), Language::from_tag("EN_us"));
+        assert_eq!(None, Language::from_tag("ja"));
+        assert_eq!(None, Language::from_tag(""));
+
+        assert_eq!(Some(Language::German), Language::detect("Der Hund ist nicht mit dem Ball auf die Straße gelaufen."));
+        assert_eq!(Some(Language::French), Language::detect("Le chat est sur la table avec les enfants."));
+        assert_eq!(Some(Language::Spanish), Language::detect("El perro está en la casa con los niños."));
+        assert_eq!(Some(Language::Italian), Language::detect("Il gatto è sulla tavola con gli amici della scuola."));
+        assert_eq!(Some(Language::Swedish), Language::detect("Hunden och katten är inte hemma på kvällen."));
+        assert_eq!(Some(Language::English), Language::detect("The dog and the cat were not at home."));
+        assert_eq!(None, Language::detect("Xyz qwerty."));
+
+        assert!(Language::English.is_stopword("the") && Language::English.is_stopword("don’t"));
+        assert!(Language::German.is_stopword("über") && !Language::German.is_stopword("hund"));
+
+        let paragraphs = vec![vec!["Le chat est sur la table.".to_string()]];
+        assert_eq!(Language::Swedish, document_language("<html lang=\"sv\">", &paragraphs));
+        assert_eq!(Language::French, document_language("<html lang=\"ja\">", &paragraphs));
+        assert_eq!(Language::English, document_language("", &[]));
+
+        assert_eq!("pt", language_code(Some("pt-BR"), &paragraphs));
+        assert_eq!("fr", language_code(Some(""), &paragraphs));
+        assert_eq!("zh", language_code(None, &[vec!["北京是中国的首都。".to_string()]]));
+        assert_eq!("ja", language_code(None, &[vec!["東京は日本の首都です。".to_string()]]));
+        assert_eq!("en", language_code(None, &[]));
+    }
+}
@@ -1,6 +1,40 @@
+#![cfg_attr(all(test, feature = "unstable"), feature(test))]
+
+use rayon::prelude::*;
+use serde::Deserialize;
 use wasm_bindgen::prelude::*;
 
+mod analysis;
+mod document;
+mod dom;
+mod encoding;
+#[cfg(not(target_arch = "wasm32"))]
+mod ffi;
+mod images;
 mod katana;
+mod keywords;
+mod language;
+mod links;
+mod metadata;
+mod metrics;
+#[cfg(feature = "server")]
+pub mod server;
+mod stats;
+mod summary;
+mod tables;
+mod wasm;
+
+pub use analysis::{analyze, Analysis, AnalysisSettings, AnnotatedSentence, Annotation, Category};
+pub use document::{prepare_document, Document, Paragraph, Sentence};
+pub use images::{extract_images, Image};
+pub use keywords::{keywords, Keyword, KeywordAlgorithm, KeywordOptions, Occurrence};
+pub use metadata::{extract_metadata, Heading, Metadata};
+pub use language::Language;
+pub use links::{extract_links, Link};
+pub use metrics::{readability, Formula, Readability, Score, TextStats};
+pub use stats::{statistics, ReadingRates, Stats};
+pub use summary::{summarize, Algorithm, Length, Summary, SummaryOptions, SummarySentence};
+pub use tables::{extract_tables, Table};
 
 fn unescape_html(html_string: &str) -> String {
     let replacements = [
@@ -22,7 +56,7 @@ fn unescape_html(html_string: &str) -> String {
         ("&ordf;", "ª"),
         ("&laquo;", "«"),
         ("&not;", "¬"),
-        ("&shy;", "­"),
+        ("&shy;", "\u{AD}"),
         ("&macr;", "¯"),
         ("&deg;", "°"),
         ("&plusmn;", "±"),
@@ -151,7 +185,7 @@ fn replace_abbreviations(text: &str) -> String {
         })
 }
 
-fn remove_html_tags(html_string: &str) -> String {
+fn remove_html_tags(html_string: &str, options: &Options) -> String {
     let text = regex::Regex::new(r"(?s)<!--(.*?)-->")
         .unwrap()
         .replace_all(html_string, "")
@@ -171,15 +205,23 @@ fn remove_html_tags(html_string: &str) -> String {
         .unwrap()
         .replace_all(&text, " ")
         .into_owned();
-    let text = replace_abbreviations(&text);
+    let text = if options.replace_abbreviations {
+        replace_abbreviations(&text)
+    } else {
+        text
+    };
     let text = regex::Regex::new(r"\n\s*?\n")
         .unwrap()
         .replace_all(&text, "\n\n")
         .into_owned();
-    let text = regex::Regex::new(r"\s?\[[0-9]+\]\s?")
-        .unwrap()
-        .replace_all(&text, "")
-        .into_owned();
+    let text = if options.strip_citations {
+        regex::Regex::new(r"\s?\[[0-9]+\]\s?")
+            .unwrap()
+            .replace_all(&text, "")
+            .into_owned()
+    } else {
+        text
+    };
     let text = text
         .split("\n")
         .map(|line| line.trim())
@@ -194,8 +236,72 @@ fn remove_html_tags(html_string: &str) -> String {
     text
 }
 
+/// Switches for the optional steps of the cleanup pipeline.
+///
+/// The default is what `prepare_text` does: abbreviations are replaced, citations
+/// stripped and all content is kept.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Options {
+    /// Rewrite common abbreviations ("e.g.", "Dr.") so that their dots don't end sentences
+    pub replace_abbreviations: bool,
+    /// Remove numeric citation markers such as `[12]`
+    pub strip_citations: bool,
+    /// Leave out the content of `nav`, `aside`, `form` and `footer` elements, mostly site navigation and chrome
+    pub drop_boilerplate: bool,
+    /// Keep the captions of figures and tables, as paragraphs of their own
+    pub captions: bool,
+    /// Token written in place of every formula, such as `[MATH]`. Formulas are written as linear text otherwise
+    pub math_placeholder: Option<String>,
+    /// Text kept for ruby annotations, such as the furigana of Japanese text
+    pub ruby: RubyText,
+}
+
+/// Text kept for ruby annotations
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RubyText {
+    /// Base text only: `漢字`
+    #[default]
+    Base,
+    /// Base text followed by its reading: `漢字(かんじ)`
+    Parenthesized,
+    /// Reading in place of the annotated base text: `かんじ`
+    Reading,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            replace_abbreviations: true,
+            strip_citations: true,
+            drop_boilerplate: false,
+            captions: true,
+            math_placeholder: None,
+            ruby: RubyText::Base,
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub fn prepare_text(text: &str) -> String {
+    prepare_text_with_options(text, &Options::default())
+}
+
+/// Same as `prepare_text` with explicit pipeline `options`
+pub fn prepare_text_with_options(text: &str, options: &Options) -> String {
+    prepare_paragraphs(text, options)
+        .iter()
+        .map(|p| p.as_slice().join(" "))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Cleans up the HTML `text` and segments it into paragraphs of sentences.
+///
+/// Joining sentences with spaces and paragraphs with blank lines gives
+/// the output of `prepare_text_with_options`.
+pub fn prepare_paragraphs(text: &str, options: &Options) -> Vec<Vec<String>> {
     let text = text
         .split("\n")
         .map(|line| line.trim())
@@ -203,15 +309,130 @@ pub fn prepare_text(text: &str) -> String {
         .collect::<Vec<&str>>()
         .join(" ");
 
-    let text = html2md::parse_html(&text);
+    let conversion = html2md::ConversionOptions {
+        drop_boilerplate: options.drop_boilerplate,
+        captions: options.captions,
+        math: options.math_placeholder.clone().map_or(html2md::MathMode::Text, html2md::MathMode::Placeholder),
+        ruby: match options.ruby {
+            RubyText::Base => html2md::RubyMode::Base,
+            RubyText::Parenthesized => html2md::RubyMode::Parenthesized,
+            RubyText::Reading => html2md::RubyMode::Reading,
+        },
+        ..html2md::ConversionOptions::plain_text()
+    };
+    let text = html2md::parse_html_with_options(&text, &conversion);
 
-    let text = remove_html_tags(&text);
+    let text = remove_html_tags(&text, options);
 
-    let paragraphs = katana::cut(&text);
+    katana::cut(&text)
+}
 
-    paragraphs
-        .iter()
-        .map(|p| p.as_slice().join(" "))
-        .collect::<Vec<String>>()
-        .join("\n\n")
+/// Runs `prepare_text_with_options` over many documents in parallel.
+/// Results are in the same order as `texts`.
+pub fn prepare_batch<S: AsRef<str> + Sync>(texts: &[S], options: &Options) -> Vec<String> {
+    texts
+        .par_iter()
+        .map(|text| prepare_text_with_options(text.as_ref(), options))
+        .collect()
+}
+
+/// Same as `prepare_text` but accepts raw bytes of an HTML document in any encoding.
+///
+/// The encoding is detected following the WHATWG sniffing algorithm: byte order mark,
+/// then `hint` (a charset label or the `Content-Type` header the page was served with),
+/// then `<meta charset>` declarations and finally a statistical guess.
+pub fn prepare_bytes(bytes: &[u8], hint: Option<&str>) -> String {
+    prepare_bytes_with_options(bytes, hint, &Options::default())
+}
+
+/// Same as `prepare_bytes` with explicit pipeline `options`
+pub fn prepare_bytes_with_options(bytes: &[u8], hint: Option<&str>, options: &Options) -> String {
+    prepare_text_with_options(&decode_bytes(bytes, hint), options)
+}
+
+/// Decodes an HTML document in any encoding, detected as described for `prepare_bytes`.
+/// Malformed sequences are replaced with U+FFFD.
+pub fn decode_bytes(bytes: &[u8], hint: Option<&str>) -> String {
+    encoding::decode(bytes, hint)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+
+    /// Fixtures shared with the Python and Node test suites: every `name.html`
+    /// in `tests/fixtures` must clean up to the contents of `name.txt`
+    fn fixtures() -> Vec<(String, String, String)> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let mut fixtures: Vec<(String, String, String)> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| {
+                let path = entry.unwrap().path();
+                if path.extension()? != "html" {
+                    return None;
+                }
+                let html = fs::read_to_string(&path).unwrap();
+                let expected = fs::read_to_string(path.with_extension("txt")).unwrap();
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                Some((name, html, expected.trim_end().to_string()))
+            })
+            .collect();
+        fixtures.sort();
+        assert!(!fixtures.is_empty());
+        fixtures
+    }
+
+    #[test]
+    fn shared_fixtures() {
+        for (name, html, expected) in fixtures() {
+            assert_eq!(expected, super::prepare_text(&html), "fixture {}", name);
+        }
+    }
+
+    #[test]
+    fn batch_keeps_order() {
+        let fixtures = fixtures();
+        let htmls: Vec<&str> = fixtures.iter().map(|(_, html, _)| html.as_str()).collect();
+        let expected: Vec<&str> = fixtures.iter().map(|(_, _, expected)| expected.as_str()).collect();
+        assert_eq!(expected, super::prepare_batch(&htmls, &super::Options::default()));
+    }
+
+    #[test]
+    fn paragraphs() {
+        let paragraphs = super::prepare_paragraphs("<p>First sentence. Second one!</p><p>Third?</p>", &super::Options::default());
+        assert_eq!(vec![vec!["First sentence.", "Second one!"], vec!["Third?"]], paragraphs);
+    }
+
+    #[test]
+    fn boilerplate() {
+        let html = "<nav><a href=\"/\">Home</a></nav><p>Article text.</p><footer>Copyright.</footer>";
+        assert_eq!("Home\n\nArticle text.\n\nCopyright.", super::prepare_text(html));
+        let options = super::Options { drop_boilerplate: true, ..super::Options::default() };
+        assert_eq!("Article text.", super::prepare_text_with_options(html, &options));
+    }
+
+    #[test]
+    fn captions() {
+        let html = "<figure><img src=\"chart.png\"><figcaption>Sales by year.</figcaption></figure><p>Body text.</p>";
+        assert_eq!("Sales by year.\n\nBody text.", super::prepare_text(html));
+        let options = super::Options { captions: false, ..super::Options::default() };
+        assert_eq!("Body text.", super::prepare_text_with_options(html, &options));
+    }
+
+    #[test]
+    fn math_placeholder() {
+        let html = "<p>Solve <math><msup><mi>x</mi><mn>2</mn></msup><mo>=</mo><mn>4</mn></math> now.</p>";
+        assert_eq!("Solve x^2 = 4 now.", super::prepare_text(html));
+        let options = super::Options { math_placeholder: Some("[MATH]".to_string()), ..super::Options::default() };
+        assert_eq!("Solve [MATH] now.", super::prepare_text_with_options(html, &options));
+    }
+
+    #[test]
+    fn ruby() {
+        let html = "<p><ruby>漢字<rt>かんじ</rt></ruby>を読む。</p>";
+        assert_eq!("漢字を読む。", super::prepare_text(html));
+        let options = super::Options { ruby: super::RubyText::Reading, ..super::Options::default() };
+        assert_eq!("かんじを読む。", super::prepare_text_with_options(html, &options));
+    }
 }
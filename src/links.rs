@@ -0,0 +1,177 @@
+use html2md::common::{get_tag_attr, tag_name};
+use markup5ever_rcdom::{Handle, NodeData};
+use serde::Serialize;
+use std::rc::Rc;
+use url::Url;
+
+use super::dom::{ancestors, descendants, parse_dom};
+
+/// Hyperlink found in the HTML markup
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    /// Anchor text with whitespace collapsed, or the alt text of the image the link wraps
+    pub text: String,
+    /// Absolute URL of the link, resolved against the `<base>` of the page and the page URL.
+    /// Relative references are kept as written when neither is known
+    pub url: String,
+    /// Lowercased `rel` keywords, such as `nofollow` or `sponsored`
+    pub rel: Vec<String>,
+    /// Whether the link is in the main content of the page rather than in its navigation,
+    /// sidebars, footer or forms
+    pub in_content: bool,
+}
+
+/// Extracts the links of `html` in document order, `a` and `area` elements with an `href` that
+/// isn't empty or a `javascript:` one. `base` is the URL of the page, relative links are resolved against it.
+///
+/// The main content is the `main` element, or the element with `role="main"`, or the only `article`,
+/// or else the whole body
+pub fn extract_links(html: &str, base: Option<&str>) -> Vec<Link> {
+    let dom = parse_dom(html);
+
+    let page = base.and_then(|base| Url::parse(base).ok());
+    let declared = descendants(&dom.document).find_map(|node| get_tag_attr(&node, "href").filter(|_| tag_name(&node) == "base"));
+    let base = match declared {
+        Some(href) => page.as_ref().map_or_else(|| Url::parse(href.trim()), |page| page.join(href.trim())).ok().or(page),
+        None => page,
+    };
+    let main = main_content(&dom.document);
+
+    let mut links = vec![];
+    for anchor in descendants(&dom.document).filter(|node| matches!(tag_name(node).as_str(), "a" | "area")) {
+        let href = match get_tag_attr(&anchor, "href") {
+            Some(href) if !href.trim().is_empty() && !href.trim().to_lowercase().starts_with("javascript:") => href.trim().to_string(),
+            _ => continue,
+        };
+        let resolved = match &base {
+            Some(base) => base.join(&href),
+            None => Url::parse(&href),
+        };
+
+        links.push(Link {
+            text: anchor_text(&anchor),
+            url: resolved.map_or(href, String::from),
+            rel: get_tag_attr(&anchor, "rel").map_or(vec![], |rel| rel.split_whitespace().map(str::to_lowercase).collect()),
+            in_content: in_content(&anchor, &main),
+        });
+    }
+    links
+}
+
+fn main_content(document: &Handle) -> Option<Handle> {
+    let main = descendants(document).find(|node| tag_name(node) == "main").or_else(|| {
+        descendants(document).find(|node| get_tag_attr(node, "role").is_some_and(|role| role.eq_ignore_ascii_case("main")))
+    });
+    if main.is_some() {
+        return main;
+    }
+
+    // articles nested in another one don't count
+    let is_article = |node: &Handle| tag_name(node) == "article";
+    let mut articles: Vec<Handle> = descendants(document).filter(|node| is_article(node) && !ancestors(node).any(|ancestor| is_article(&ancestor))).collect();
+    match articles.len() {
+        1 => articles.pop(),
+        _ => descendants(document).find(|node| tag_name(node) == "body"),
+    }
+}
+
+/// Whether the link is inside the main content and outside of its navigation, sidebars, footers and forms.
+/// Headers are site chrome too, unless they head an article
+fn in_content(anchor: &Handle, main: &Option<Handle>) -> bool {
+    let main = match main {
+        Some(main) => main,
+        None => return false,
+    };
+
+    let ancestors: Vec<Handle> = ancestors(anchor).collect();
+    let mut in_main = false;
+    for (index, ancestor) in ancestors.iter().enumerate() {
+        if Rc::ptr_eq(ancestor, main) {
+            in_main = true;
+            break;
+        }
+        match tag_name(ancestor).as_str() {
+            "nav" | "aside" | "footer" | "form" => return false,
+            "header" if !ancestors[index..].iter().any(|node| tag_name(node) == "article") => return false,
+            _ => {}
+        }
+    }
+    in_main
+}
+
+/// Collapsed text of the link, or the alt text of its images when it has no text
+fn anchor_text(anchor: &Handle) -> String {
+    fn gather(node: &Handle, text: &mut String, alts: &mut Vec<String>) {
+        match node.data {
+            NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+            NodeData::Element { ref name, .. } => match name.local.as_ref() {
+                "script" | "style" => {}
+                "img" => alts.extend(get_tag_attr(node, "alt")),
+                _ => {
+                    for child in node.children.borrow().iter() {
+                        gather(child, text, alts);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    let mut text = String::new();
+    let mut alts = vec![];
+    gather(anchor, &mut text, &mut alts);
+    if text.trim().is_empty() {
+        text = alts.join(" ");
+    }
+    if text.trim().is_empty() {
+        text = get_tag_attr(anchor, "aria-label").or_else(|| get_tag_attr(anchor, "title")).unwrap_or_default();
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{extract_links, Link};
+
+    #[test]
+    fn resolution_and_main_content() {
+        let links = extract_links(
+            r#"<head><base href="/blog/"></head>
+            <header><a href="/">Home</a></header>
+            <article>
+                <header><a href="authors/ann" rel="Author">Ann</a></header>
+                <p>See <a href="https://example.org/paper.pdf" rel="nofollow noopener">the
+                    paper</a> and <a href="../about#team"><img src="team.jpg" alt="our team"></a>.</p>
+                <aside><a href="related">Related</a></aside>
+                <a href="javascript:void(0)">Share</a><a href="">Empty</a>
+            </article>
+            <footer><a href="mailto:editor@example.com" title="Contact">✉</a></footer>"#,
+            Some("https://example.com/blog/2024/post.html"),
+        );
+        let link = |text: &str, url: &str, rel: &[&str], in_content: bool| Link {
+            text: text.to_string(),
+            url: url.to_string(),
+            rel: rel.iter().map(|rel| rel.to_string()).collect(),
+            in_content,
+        };
+        assert_eq!(
+            vec![
+                link("Home", "https://example.com/", &[], false),
+                link("Ann", "https://example.com/blog/authors/ann", &["author"], true),
+                link("the paper", "https://example.org/paper.pdf", &["nofollow", "noopener"], true),
+                link("our team", "https://example.com/about#team", &[], true),
+                link("Related", "https://example.com/blog/related", &[], false),
+                link("✉", "mailto:editor@example.com", &[], false),
+            ],
+            links
+        );
+    }
+
+    #[test]
+    fn without_base() {
+        let links = extract_links(r#"<main><a href="/docs">Docs</a></main><nav><a href="https://example.com/">Home</a></nav>"#, None);
+        let urls: Vec<(&str, bool)> = links.iter().map(|link| (link.url.as_str(), link.in_content)).collect();
+        assert_eq!(vec![("/docs", true), ("https://example.com/", false)], urls);
+    }
+}
@@ -0,0 +1,183 @@
+use html2md::common::{get_tag_attr, text_content};
+use markup5ever_rcdom::{Handle, NodeData};
+use serde::Serialize;
+
+use super::dom::parse_dom;
+
+/// Document level information found in the HTML markup
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    /// `og:title`, `<title>` or the first `<h1>`, in this order
+    pub title: Option<String>,
+    /// Author as declared by `<meta name="author">` or `article:author`
+    pub byline: Option<String>,
+    /// `og:description` or `<meta name="description">`
+    pub description: Option<String>,
+    /// Declared language tag, such as `en` or `pt-BR`
+    pub language: Option<String>,
+    /// `og:site_name`
+    pub site_name: Option<String>,
+    /// Publication date as found in the markup, usually ISO 8601
+    pub published: Option<String>,
+    /// Canonical URL of the document
+    pub url: Option<String>,
+    /// `<meta name="keywords">` split on commas
+    pub keywords: Vec<String>,
+    /// Headings of the document in order of appearance
+    pub headings: Vec<Heading>,
+}
+
+/// Text of a `<h1>`..`<h6>` element
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+}
+
+/// Everything collected while walking the DOM, resolved into `Metadata` afterwards
+#[derive(Default)]
+struct Collected {
+    title: Option<String>,
+    lang: Option<String>,
+    canonical: Option<String>,
+    /// `(name or property, content)` of all `<meta>` tags, names lowercased
+    meta: Vec<(String, String)>,
+    headings: Vec<Heading>,
+}
+
+impl Collected {
+    fn meta(&self, keys: &[&str]) -> Option<String> {
+        keys.iter().find_map(|key| {
+            self.meta
+                .iter()
+                .find(|(name, content)| name == key && !content.is_empty())
+                .map(|(_, content)| content.clone())
+        })
+    }
+}
+
+/// Extracts title, byline, language and other document level information from `html`
+pub fn extract_metadata(html: &str) -> Metadata {
+    let dom = parse_dom(html);
+
+    let mut collected = Collected::default();
+    collect(&dom.document, &mut collected);
+
+    let first_h1 = collected.headings.iter().find(|heading| heading.level == 1).map(|heading| heading.text.clone());
+    Metadata {
+        title: collected.meta(&["og:title", "twitter:title"]).or(collected.title.clone()).or(first_h1),
+        byline: collected.meta(&["author", "article:author", "dc.creator", "byl"]),
+        description: collected.meta(&["og:description", "description", "twitter:description"]),
+        language: collected
+            .lang
+            .clone()
+            .or_else(|| collected.meta(&["content-language", "og:locale"]))
+            .map(|lang| lang.replace('_', "-")),
+        site_name: collected.meta(&["og:site_name"]),
+        published: collected.meta(&["article:published_time", "datepublished", "date", "dc.date"]),
+        url: collected.canonical.clone().or_else(|| collected.meta(&["og:url"])),
+        keywords: collected
+            .meta(&["keywords"])
+            .map(|keywords| {
+                keywords
+                    .split(',')
+                    .map(|keyword| keyword.trim().to_string())
+                    .filter(|keyword| !keyword.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        headings: collected.headings,
+    }
+}
+
+fn collect(node: &Handle, collected: &mut Collected) {
+    if let NodeData::Element { ref name, .. } = node.data {
+        let attr = |name: &str| get_tag_attr(node, name);
+        match name.local.as_ref() {
+            "html" => collected.lang = attr("lang").filter(|lang| !lang.is_empty()),
+            "title" if collected.title.is_none() => {
+                collected.title = Some(text_content(node, &["script", "style"], true)).filter(|title| !title.is_empty())
+            }
+            "meta" => {
+                let key = attr("property")
+                    .or_else(|| attr("name"))
+                    .or_else(|| attr("http-equiv"))
+                    .or_else(|| attr("itemprop"));
+                if let (Some(key), Some(content)) = (key, attr("content")) {
+                    collected.meta.push((key.to_lowercase(), content.trim().to_string()));
+                }
+            }
+            "link" => {
+                let canonical = attr("rel").is_some_and(|rel| rel.eq_ignore_ascii_case("canonical"));
+                if canonical && collected.canonical.is_none() {
+                    collected.canonical = attr("href");
+                }
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let text = text_content(node, &["script", "style"], true);
+                if !text.is_empty() {
+                    let level = name.local.as_bytes()[1] - b'0';
+                    collected.headings.push(Heading { level, text });
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    for child in node.children.borrow().iter() {
+        collect(child, collected);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{extract_metadata, Heading};
+
+    #[test]
+    fn head_metadata() {
+        let metadata = extract_metadata(r#"<!DOCTYPE html>
+            <html lang="de_AT">
+            <head>
+                <title>Fallback title</title>
+                <meta property="og:title" content="Der Titel">
+                <meta name="author" content="Anna Huber">
+                <meta name="description" content="Eine Beschreibung.">
+                <meta property="og:site_name" content="Zeitung">
+                <meta property="article:published_time" content="2024-03-01T08:00:00Z">
+                <meta name="keywords" content="Wien, Kaffee, ,Kultur">
+                <link rel="canonical" href="https://example.com/artikel">
+            </head>
+            <body><h1>Der  Titel</h1><p>Text</p><h2>Ab<em>satz</em></h2></body>
+            </html>"#);
+
+        assert_eq!(Some("Der Titel".to_string()), metadata.title);
+        assert_eq!(Some("Anna Huber".to_string()), metadata.byline);
+        assert_eq!(Some("Eine Beschreibung.".to_string()), metadata.description);
+        assert_eq!(Some("de-AT".to_string()), metadata.language);
+        assert_eq!(Some("Zeitung".to_string()), metadata.site_name);
+        assert_eq!(Some("2024-03-01T08:00:00Z".to_string()), metadata.published);
+        assert_eq!(Some("https://example.com/artikel".to_string()), metadata.url);
+        assert_eq!(vec!["Wien", "Kaffee", "Kultur"], metadata.keywords);
+        assert_eq!(
+            vec![
+                Heading { level: 1, text: "Der Titel".to_string() },
+                Heading { level: 2, text: "Absatz".to_string() }
+            ],
+            metadata.headings
+        );
+    }
+
+    #[test]
+    fn fallbacks() {
+        let metadata = extract_metadata("<body><h2>Second</h2><h1>First heading</h1></body>");
+        assert_eq!(Some("First heading".to_string()), metadata.title);
+        assert_eq!(None, metadata.language);
+        assert!(metadata.keywords.is_empty());
+
+        let metadata = extract_metadata("<title> Page\n title </title>");
+        assert_eq!(Some("Page title".to_string()), metadata.title);
+    }
+}
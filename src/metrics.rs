@@ -0,0 +1,360 @@
+//! Readability scores of the cleaned up text.
+//!
+//! Sentences are the ones found by `katana::cut`, so the scores are computed on exactly what
+//! `prepare_paragraphs` returns. Syllables are estimated with spelling heuristics of the
+//! document language.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use super::language::{document_language, Language};
+use super::stats::is_cjk;
+use super::Options;
+
+mod syllables;
+
+lazy_static! {
+    /// Words familiar to fourth-graders from the New Dale–Chall list
+    static ref DALE_CHALL_WORDS: HashSet<&'static str> = include_str!("metrics/dale_chall.txt").split_whitespace().collect();
+}
+
+/// Counts the readability formulas are based on
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextStats {
+    pub sentences: usize,
+    pub words: usize,
+    pub syllables: usize,
+    /// Letters and digits of all words
+    pub letters: usize,
+    /// Words of one syllable
+    pub monosyllables: usize,
+    /// Words of three or more syllables
+    pub polysyllables: usize,
+    /// Polysyllables without proper nouns and words that only reach three syllables
+    /// through an `-es`, `-ed` or `-ing` ending, as defined by Gunning
+    pub complex_words: usize,
+    /// Words of more than six letters
+    pub long_words: usize,
+    /// Words missing from the Dale–Chall list of familiar English words
+    pub difficult_words: usize,
+}
+
+/// Readability formulas made for languages other than English
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Formula {
+    /// German adaptation of Flesch Reading Ease, 0 (very difficult) to 100 (very easy)
+    Amstad,
+    /// First Wiener Sachtextformel, German school grade 4 to 15
+    WienerSachtextformel,
+    /// French adaptation of Flesch Reading Ease
+    KandelMoles,
+    /// Spanish adaptation of Flesch Reading Ease, in Law's corrected form
+    FernandezHuerta,
+    /// Spanish perspicuity index, 0 to 100
+    SzigrisztPazos,
+    /// Italian, 0 (very difficult) to 100 (very easy)
+    Gulpease,
+    /// Läsbarhetsindex, language independent, above 60 is very difficult
+    Lix,
+    /// Long words per sentence, language independent
+    Rix,
+}
+
+impl Formula {
+    /// Formulas that apply to `language`, LIX and RIX apply to all languages
+    pub fn for_language(language: Language) -> &'static [Formula] {
+        match language {
+            Language::English | Language::Swedish => &[Formula::Lix, Formula::Rix],
+            Language::German => &[Formula::Amstad, Formula::WienerSachtextformel, Formula::Lix, Formula::Rix],
+            Language::French => &[Formula::KandelMoles, Formula::Lix, Formula::Rix],
+            Language::Spanish => &[Formula::FernandezHuerta, Formula::SzigrisztPazos, Formula::Lix, Formula::Rix],
+            Language::Italian => &[Formula::Gulpease, Formula::Lix, Formula::Rix],
+        }
+    }
+
+    /// Applies the formula to `stats`, which must contain words and sentences
+    fn score(self, stats: &TextStats) -> f64 {
+        let words = stats.words as f64;
+        let sentences = stats.sentences as f64;
+        let words_per_sentence = words / sentences;
+        let syllables_per_word = stats.syllables as f64 / words;
+        let percentage = |count: usize| 100.0 * count as f64 / words;
+
+        match self {
+            Formula::Amstad => 180.0 - words_per_sentence - 58.5 * syllables_per_word,
+            Formula::WienerSachtextformel => {
+                0.1935 * percentage(stats.polysyllables) + 0.1672 * words_per_sentence + 0.1297 * percentage(stats.long_words)
+                    - 0.0327 * percentage(stats.monosyllables)
+                    - 0.875
+            }
+            Formula::KandelMoles => 207.0 - 1.015 * words_per_sentence - 73.6 * syllables_per_word,
+            Formula::FernandezHuerta => 206.84 - 60.0 * syllables_per_word - 1.02 * words_per_sentence,
+            Formula::SzigrisztPazos => 206.835 - 62.3 * syllables_per_word - words_per_sentence,
+            Formula::Gulpease => 89.0 + (300.0 * sentences - 10.0 * stats.letters as f64) / words,
+            Formula::Lix => words_per_sentence + percentage(stats.long_words),
+            Formula::Rix => stats.long_words as f64 / sentences,
+        }
+    }
+}
+
+/// Result of a language specific formula
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Score {
+    pub formula: Formula,
+    pub value: f64,
+}
+
+/// Readability of a text according to the usual English formulas and
+/// the formulas made for its language
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Readability {
+    /// Language the text was analysed as
+    pub language: Language,
+    pub stats: TextStats,
+    /// 0 (very difficult) to 100 (very easy)
+    pub flesch_reading_ease: f64,
+    /// This and the following scores are US school grade levels
+    pub flesch_kincaid_grade: f64,
+    pub gunning_fog: f64,
+    pub smog: f64,
+    pub automated_readability_index: f64,
+    pub coleman_liau: f64,
+    /// Adjusted score, 4.9 and below is easily understood by a fourth-grader
+    pub dale_chall: f64,
+    /// Scores of `Formula::for_language`
+    pub language_scores: Vec<Score>,
+}
+
+/// Cleans up `html` and scores the readability of the result.
+///
+/// The language is the declared language of the document if supported,
+/// otherwise it is detected from the text, falling back to English.
+pub fn readability(html: &str, options: &Options) -> Readability {
+    let paragraphs = super::prepare_paragraphs(html, options);
+    let language = document_language(html, &paragraphs);
+    Readability::of(TextStats::of(&paragraphs, language), language)
+}
+
+impl TextStats {
+    /// Counts words and syllables of the paragraphs produced by `prepare_paragraphs`
+    pub fn of(paragraphs: &[Vec<String>], language: Language) -> Self {
+        let mut stats = TextStats::default();
+        for sentence in paragraphs.iter().flatten() {
+            let mut words = words(sentence).peekable();
+            if words.peek().is_none() {
+                continue;
+            }
+            stats.sentences += 1;
+
+            for (position, word) in words.enumerate() {
+                let syllables = syllables::count(word, language);
+                let letters = word.chars().filter(|ch| ch.is_alphanumeric()).count();
+                stats.words += 1;
+                stats.syllables += syllables;
+                stats.letters += letters;
+                if syllables == 1 {
+                    stats.monosyllables += 1;
+                }
+                if syllables >= 3 {
+                    stats.polysyllables += 1;
+                    if is_complex(word, position) {
+                        stats.complex_words += 1;
+                    }
+                }
+                if letters > 6 {
+                    stats.long_words += 1;
+                }
+                if !is_familiar(word) {
+                    stats.difficult_words += 1;
+                }
+            }
+        }
+        stats
+    }
+}
+
+impl Readability {
+    /// Applies the formulas to `stats`, all scores are 0 for a text without words
+    pub fn of(stats: TextStats, language: Language) -> Self {
+        if stats.words == 0 || stats.sentences == 0 {
+            return Readability { language, stats, ..Readability::default() };
+        }
+
+        let words = stats.words as f64;
+        let sentences = stats.sentences as f64;
+        let words_per_sentence = words / sentences;
+        let syllables_per_word = stats.syllables as f64 / words;
+        let difficult_percentage = 100.0 * stats.difficult_words as f64 / words;
+
+        Readability {
+            language,
+            flesch_reading_ease: 206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+            flesch_kincaid_grade: 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59,
+            gunning_fog: 0.4 * (words_per_sentence + 100.0 * stats.complex_words as f64 / words),
+            smog: 1.043 * (stats.polysyllables as f64 * 30.0 / sentences).sqrt() + 3.1291,
+            automated_readability_index: 4.71 * stats.letters as f64 / words + 0.5 * words_per_sentence - 21.43,
+            coleman_liau: 0.0588 * (100.0 * stats.letters as f64 / words) - 0.296 * (100.0 * sentences / words) - 15.8,
+            dale_chall: 0.1579 * difficult_percentage
+                + 0.0496 * words_per_sentence
+                + if difficult_percentage > 5.0 { 3.6365 } else { 0.0 },
+            language_scores: Formula::for_language(language)
+                .iter()
+                .map(|&formula| Score { formula, value: formula.score(&stats) })
+                .collect(),
+            stats,
+        }
+    }
+
+    /// Score of `formula`, if it applies to the language of the text
+    pub fn score(&self, formula: Formula) -> Option<f64> {
+        self.language_scores.iter().find(|score| score.formula == formula).map(|score| score.value)
+    }
+}
+
+/// Words of `text`, the tokenizer shared by all measures of the cleaned text
+pub(crate) fn words(text: &str) -> impl Iterator<Item = &str> {
+    word_ranges(text).map(move |range| &text[range])
+}
+
+/// Byte ranges of the runs of letters and digits in `text`. Apostrophes and hyphens inside
+/// a word and separators inside a number don't end it, every CJK character is a word on its own
+pub(crate) fn word_ranges(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, first) = chars.find(|(_, ch)| ch.is_alphanumeric())?;
+        let (mut end, mut last) = (start + first.len_utf8(), first);
+        while !is_cjk(last) {
+            let Some(&(index, ch)) = chars.peek() else { break };
+            let next = text[index + ch.len_utf8()..].chars().next();
+            let joins = ch.is_alphanumeric()
+                || ("'’-".contains(ch) && next.is_some_and(|next| next.is_alphanumeric()))
+                || (".,".contains(ch) && last.is_ascii_digit() && next.is_some_and(|next| next.is_ascii_digit()));
+            if !joins || is_cjk(ch) {
+                break;
+            }
+            chars.next();
+            (end, last) = (index + ch.len_utf8(), ch);
+        }
+        Some(start..end)
+    })
+}
+
+/// Gunning's complex words: `position` is the index of `word` within its sentence
+fn is_complex(word: &str, position: usize) -> bool {
+    let proper_noun = position > 0 && word.starts_with(char::is_uppercase);
+    if proper_noun || word.contains('-') {
+        return false;
+    }
+    let lowercase = word.to_lowercase();
+    ["es", "ed", "ing"]
+        .iter()
+        .filter_map(|suffix| lowercase.strip_suffix(suffix))
+        .all(|stem| syllables::count(stem, Language::English) >= 3)
+}
+
+/// Whether `word` or the base form of a regular inflection of it is on the Dale–Chall list
+fn is_familiar(word: &str) -> bool {
+    if word.chars().all(|ch| ch.is_ascii_digit()) {
+        return true;
+    }
+    let word = word.to_lowercase().replace('’', "'");
+    if DALE_CHALL_WORDS.contains(word.as_str()) {
+        return true;
+    }
+
+    let inflections: [(&str, &str); 10] = [
+        ("s", ""),
+        ("es", ""),
+        ("ies", "y"),
+        ("ed", ""),
+        ("ed", "e"),
+        ("ied", "y"),
+        ("ing", ""),
+        ("ing", "e"),
+        ("er", ""),
+        ("est", ""),
+    ];
+    inflections.iter().any(|(suffix, replacement)| {
+        word.strip_suffix(suffix)
+            .is_some_and(|stem| !stem.is_empty() && DALE_CHALL_WORDS.contains(format!("{}{}", stem, replacement).as_str()))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{readability, words, Formula, Readability, TextStats};
+    use crate::{Language, Options};
+
+    #[test]
+    fn scores() {
+        let paragraphs = vec![
+            vec!["The cat sat on the mat.".to_string(), "It was happy.".to_string()],
+            vec!["Considerable administrative complications necessitated Jennifer's reconsideration.".to_string()],
+        ];
+        let stats = TextStats::of(&paragraphs, Language::English);
+        assert_eq!(3, stats.sentences);
+        assert_eq!(15, stats.words);
+        assert_eq!(6, stats.polysyllables);
+        // "Jennifer's" is a proper noun
+        assert_eq!(5, stats.complex_words);
+        assert_eq!(6, stats.difficult_words);
+
+        let readability = Readability::of(stats.clone(), Language::English);
+        let words_per_sentence = 15.0 / 3.0;
+        let syllables_per_word = stats.syllables as f64 / 15.0;
+        assert!((readability.flesch_reading_ease - (206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word)).abs() < 1e-9);
+        assert!(readability.flesch_kincaid_grade > 0.0);
+        assert!(readability.dale_chall > 3.6365);
+        let lix = words_per_sentence + 100.0 * stats.long_words as f64 / 15.0;
+        assert_eq!(Some(lix), readability.score(Formula::Lix));
+        assert_eq!(None, readability.score(Formula::Amstad));
+
+        let simple = Readability::of(TextStats::of(&[vec!["The dog ran home.".to_string()]], Language::English), Language::English);
+        assert!(simple.flesch_reading_ease > readability.flesch_reading_ease);
+        assert!(simple.gunning_fog < readability.gunning_fog);
+        assert_eq!(0, simple.stats.difficult_words);
+
+        assert_eq!(Readability::default(), Readability::of(TextStats::of(&[], Language::English), Language::English));
+    }
+
+    #[test]
+    fn tokenizer() {
+        let tokenized = |text| words(text).collect::<Vec<_>>();
+        assert_eq!(vec!["Don't", "re-read", "it", "3.14", "times", "1,000", "x"], tokenized("Don't re-read it 3.14 times, (1,000)... -x-"));
+        assert_eq!(vec!["and", "or", "end"], tokenized("and/or end."));
+        assert_eq!(vec!["東", "京", "は", "Tokyo", "で", "す", "2024"], tokenized("東京はTokyo です。2024"));
+    }
+
+    #[test]
+    fn language_formulas() {
+        let german = readability(
+            "<p>Der Hund ist nicht mit dem Ball auf die Straße gelaufen. Die Verständlichkeit dieses Satzes ist hoch.</p>",
+            &Options::default(),
+        );
+        assert_eq!(Language::German, german.language);
+        let formulas: Vec<Formula> = german.language_scores.iter().map(|score| score.formula).collect();
+        assert_eq!(vec![Formula::Amstad, Formula::WienerSachtextformel, Formula::Lix, Formula::Rix], formulas);
+        let stats = &german.stats;
+        let amstad = 180.0 - stats.words as f64 / stats.sentences as f64 - 58.5 * stats.syllables as f64 / stats.words as f64;
+        assert_eq!(Some(amstad), german.score(Formula::Amstad));
+
+        let spanish = readability("<html lang=\"es\"><p>El perro está en la casa.</p></html>", &Options::default());
+        assert_eq!(Language::Spanish, spanish.language);
+        assert!(spanish.score(Formula::FernandezHuerta).unwrap() > spanish.score(Formula::SzigrisztPazos).unwrap());
+
+        let italian = readability("<html lang=\"it\"><p>Il gatto è sulla tavola.</p></html>", &Options::default());
+        let gulpease = 89.0 + (300.0 - 10.0 * italian.stats.letters as f64) / italian.stats.words as f64;
+        assert_eq!(Some(gulpease), italian.score(Formula::Gulpease));
+
+        let french = readability("<p>Le chat est sur la table avec les enfants.</p>", &Options::default());
+        assert_eq!(Language::French, french.language);
+        assert!(french.score(Formula::KandelMoles).is_some());
+    }
+}
@@ -0,0 +1,141 @@
+//! Syllable estimates from spelling, one heuristic per language.
+
+use crate::Language;
+
+/// Estimates the syllables of `word` in `language`, at least one
+pub(crate) fn count(word: &str, language: Language) -> usize {
+    let word: Vec<char> = word
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if word.is_empty() {
+        return 1;
+    }
+
+    let groups = match language {
+        Language::English => return english(&word),
+        Language::German => vowel_groups(&word, "aeiouyäöü"),
+        Language::French => french(&word),
+        Language::Spanish => hiatus_aware(&word, "aeiouáéíóúü", "aeoáéíóú"),
+        Language::Italian => hiatus_aware(&word, "aeiouàèéìíòóù", "aeoàèéìíòóù"),
+        Language::Swedish => vowel_groups(&word, "aeiouyåäö"),
+    };
+    groups.max(1)
+}
+
+fn english(word: &[char]) -> usize {
+    if word.len() <= 3 {
+        return 1;
+    }
+
+    let vowels = "aeiouyàáâäèéêëìíîïòóôöùúûü";
+    let is_vowel = |ch: char| vowels.contains(ch);
+    let mut end = word.len();
+    match word[end - 2..] {
+        // "makes", "stones", but "horses", "boxes", "changes", "tables"
+        ['e', 's'] if !is_vowel(word[end - 3]) && !"sxzcghl".contains(word[end - 3]) => end -= 2,
+        // "jumped", "played", but "wanted", "needed"
+        ['e', 'd'] if !"td".contains(word[end - 3]) => end -= 2,
+        // silent e of "make", but "table"
+        [consonant, 'e'] if !is_vowel(consonant) && consonant != 'l' => end -= 1,
+        _ => {}
+    }
+    // consonant y of "yellow"
+    let start = usize::from(word[0] == 'y');
+
+    vowel_groups(&word[start..end], vowels).max(1)
+}
+
+/// Mute final `e` and `es`, as in "table" and "tables"
+fn french(word: &[char]) -> usize {
+    let vowels = "aeiouyàâéèêëîïôûùüÿœæ";
+    let mut end = word.len();
+    if word.ends_with(&['e', 's']) {
+        end -= 2;
+    } else if word.ends_with(&['e']) {
+        end -= 1;
+    }
+    match vowel_groups(&word[..end], vowels) {
+        0 => vowel_groups(word, vowels),
+        groups => groups,
+    }
+}
+
+/// Adjacent vowels form one syllable unless both are `strong`, `u` is mute in "que", "gui"
+fn hiatus_aware(word: &[char], vowels: &str, strong: &str) -> usize {
+    let mut syllables = 0;
+    let mut previous: Option<char> = None;
+    for (index, &ch) in word.iter().enumerate() {
+        if !vowels.contains(ch) {
+            previous = None;
+            continue;
+        }
+        let mute_u = ch == 'u'
+            && index > 0
+            && "qg".contains(word[index - 1])
+            && word.get(index + 1).is_some_and(|next| "eiéí".contains(*next));
+        if mute_u {
+            continue;
+        }
+        match previous {
+            Some(before) if !(strong.contains(before) && strong.contains(ch)) => {}
+            _ => syllables += 1,
+        }
+        previous = Some(ch);
+    }
+    syllables
+}
+
+fn vowel_groups(word: &[char], vowels: &str) -> usize {
+    let mut groups = 0;
+    let mut previous_vowel = false;
+    for &ch in word {
+        let vowel = vowels.contains(ch);
+        if vowel && !previous_vowel {
+            groups += 1;
+        }
+        previous_vowel = vowel;
+    }
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use super::count;
+    use crate::Language;
+
+    #[test]
+    fn estimates() {
+        let expected = [
+            (Language::English, "cat", 1),
+            (Language::English, "make", 1),
+            (Language::English, "jumped", 1),
+            (Language::English, "wanted", 2),
+            (Language::English, "horses", 2),
+            (Language::English, "table", 2),
+            (Language::English, "yellow", 2),
+            (Language::English, "beautiful", 3),
+            (Language::English, "readability", 5),
+            (Language::German, "Haus", 1),
+            (Language::German, "Straße", 2),
+            (Language::German, "Schönheit", 2),
+            (Language::German, "Verständlichkeit", 4),
+            (Language::French, "table", 1),
+            (Language::French, "maisons", 2),
+            (Language::French, "lisibilité", 5),
+            (Language::French, "oiseau", 2),
+            (Language::Spanish, "queso", 2),
+            (Language::Spanish, "poeta", 3),
+            (Language::Spanish, "ciudad", 2),
+            (Language::Spanish, "día", 2),
+            (Language::Spanish, "legibilidad", 5),
+            (Language::Italian, "leggibilità", 5),
+            (Language::Italian, "poeta", 3),
+            (Language::Swedish, "läsbarhet", 3),
+        ];
+        for (language, word, syllables) in expected {
+            assert_eq!(syllables, count(word, language), "{:?} {}", language, word);
+        }
+    }
+}
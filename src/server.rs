@@ -0,0 +1,499 @@
+//! Minimal HTTP/1.1 service exposing the cleanup pipeline to non-Rust programs.
+//!
+//! Every endpoint accepts a `POST` with either an HTML body (decoded according to its
+//! `Content-Type` charset, or sniffed) or a JSON body `{"html": "...", "options": {...}}`
+//! and answers with JSON:
+//!
+//! * `POST /clean` - `{"text": "..."}`
+//! * `POST /segment` - `{"paragraphs": [["sentence", ...], ...]}`
+//! * `POST /metadata` - document metadata
+//! * `POST /stats` - document statistics, reading times use the `rates` of a JSON body
+//! * `GET /health` - `{"status": "ok"}`
+//!
+//! Connections serve a single request and are closed afterwards.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{encoding, Options, ReadingRates};
+
+/// Longest request line or header line accepted
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 100;
+/// How often the accept loop checks for shutdown
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Limits of the service
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Requests with larger bodies are rejected with 413
+    pub max_body_bytes: usize,
+    /// Time allowed to receive a request, counted from accepting its connection, and socket
+    /// write timeout. Slow clients get 408
+    pub io_timeout: Duration,
+    /// Requests taking longer to process get 503
+    pub processing_timeout: Duration,
+    /// Connections beyond this are rejected with 503. A connection keeps its slot until
+    /// its processing is done, even after answering that it timed out
+    pub max_connections: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_body_bytes: 5 * 1024 * 1024,
+            io_timeout: Duration::from_secs(10),
+            processing_timeout: Duration::from_secs(30),
+            max_connections: 64,
+        }
+    }
+}
+
+/// Accepts connections on `listener` until `shutdown` is set, then waits
+/// for in-flight requests and their processing to complete before returning.
+pub fn serve(listener: TcpListener, config: Config, shutdown: Arc<AtomicBool>) -> io::Result<()> {
+    listener.set_nonblocking(true)?;
+    let active = Arc::new(AtomicUsize::new(0));
+    let mut workers: Vec<thread::JoinHandle<()>> = vec![];
+
+    while !shutdown.load(Ordering::SeqCst) {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        workers.retain(|worker| !worker.is_finished());
+        if active.load(Ordering::SeqCst) >= config.max_connections {
+            let mut stream = stream;
+            let _ = stream.set_nonblocking(false);
+            let _ = stream.set_write_timeout(Some(config.io_timeout));
+            let _ = respond(&mut stream, 503, &json!({"error": "too many connections"}));
+            continue;
+        }
+
+        active.fetch_add(1, Ordering::SeqCst);
+        let deadline = Instant::now() + config.io_timeout;
+        let config = config.clone();
+        let active = Arc::clone(&active);
+        workers.push(thread::spawn(move || {
+            handle_connection(stream, deadline, &config);
+            active.fetch_sub(1, Ordering::SeqCst);
+        }));
+    }
+
+    for worker in workers {
+        let _ = worker.join();
+    }
+    Ok(())
+}
+
+/// Request parsed off the wire
+struct Request {
+    method: String,
+    path: String,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Error responses produced while handling a request
+struct Failure(u16, String);
+
+/// Processing of a request, run apart from its connection to bound its duration
+type Job = Box<dyn FnOnce() -> Value + Send>;
+
+impl Failure {
+    fn new(status: u16, message: &str) -> Self {
+        Failure(status, message.to_string())
+    }
+}
+
+/// Reads from a connection until the deadline of its request has passed
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, deadline: Instant, config: &Config) {
+    let setup = stream.set_nonblocking(false).and_then(|_| stream.set_write_timeout(Some(config.io_timeout)));
+    if setup.is_err() {
+        return;
+    }
+
+    let mut worker = None;
+    let result = read_request(DeadlineReader { stream: &stream, deadline }, config).and_then(route).and_then(|job| {
+        let (sender, receiver) = mpsc::channel();
+        worker = Some(thread::spawn(move || {
+            let _ = sender.send(job());
+        }));
+        receiver.recv_timeout(config.processing_timeout).map_err(|err| match err {
+            mpsc::RecvTimeoutError::Timeout => Failure::new(503, "processing timed out"),
+            mpsc::RecvTimeoutError::Disconnected => Failure::new(500, "processing failed"),
+        })
+    });
+    let (status, body) = match result {
+        Ok(body) => (200, body),
+        Err(Failure(status, message)) => (status, json!({ "error": message })),
+    };
+    let _ = respond(&mut stream, status, &body);
+    drop(stream);
+
+    // the pipeline can't be interrupted, a timed out job keeps the connection slot until it's done
+    if let Some(worker) = worker {
+        let _ = worker.join();
+    }
+}
+
+fn read_request(stream: DeadlineReader, config: &Config) -> Result<Request, Failure> {
+    let mut reader = BufReader::new(stream);
+
+    let request_line = read_line(&mut reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => (method.to_string(), target),
+        _ => return Err(Failure::new(400, "malformed request line")),
+    };
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut content_length = None;
+    let mut content_type = None;
+    for count in 0.. {
+        let line = read_line(&mut reader)?;
+        if line.is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Err(Failure::new(431, "too many headers"));
+        }
+        let (name, value) = line.split_once(':').ok_or_else(|| Failure::new(400, "malformed header"))?;
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = Some(value.parse::<usize>().map_err(|_| Failure::new(400, "invalid Content-Length"))?);
+        } else if name.eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Failure::new(411, "chunked bodies are not supported, send Content-Length"));
+        }
+    }
+
+    let mut body = vec![];
+    if method == "POST" {
+        let length = content_length.ok_or_else(|| Failure::new(411, "Content-Length required"))?;
+        if length > config.max_body_bytes {
+            return Err(Failure(413, format!("body exceeds {} bytes", config.max_body_bytes)));
+        }
+        body.resize(length, 0);
+        reader.read_exact(&mut body).map_err(io_failure)?;
+    }
+
+    Ok(Request { method, path, content_type, body })
+}
+
+fn read_line(reader: &mut BufReader<DeadlineReader>) -> Result<String, Failure> {
+    let mut line = vec![];
+    reader.by_ref().take(MAX_LINE_BYTES).read_until(b'\n', &mut line).map_err(io_failure)?;
+    if line.last() != Some(&b'\n') {
+        return Err(if line.len() as u64 >= MAX_LINE_BYTES {
+            Failure::new(431, "header line too long")
+        } else {
+            Failure::new(400, "unexpected end of request")
+        });
+    }
+    let line = String::from_utf8(line).map_err(|_| Failure::new(400, "headers must be UTF-8"))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn io_failure(err: io::Error) -> Failure {
+    match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => Failure::new(408, "timed out reading request"),
+        _ => Failure::new(400, "unexpected end of request"),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonBody {
+    html: String,
+    #[serde(default)]
+    options: Options,
+    #[serde(default)]
+    rates: ReadingRates,
+}
+
+fn route(request: Request) -> Result<Job, Failure> {
+    let endpoint = request.path.as_str();
+    match (request.method.as_str(), endpoint) {
+        ("GET", "/health") => return Ok(Box::new(|| json!({"status": "ok"}))),
+        #[cfg(test)]
+        ("POST", "/test/block") => {
+            let gate = String::from_utf8_lossy(&request.body).into_owned();
+            return Ok(Box::new(move || {
+                test::wait_for_release(&gate);
+                json!({})
+            }));
+        }
+        ("POST", "/clean" | "/segment" | "/metadata" | "/stats") => {}
+        (_, "/health" | "/clean" | "/segment" | "/metadata" | "/stats") => return Err(Failure::new(405, "method not allowed")),
+        _ => return Err(Failure::new(404, "not found")),
+    }
+
+    let is_json = request
+        .content_type
+        .as_deref()
+        .is_some_and(|content_type| content_type.trim_start().to_ascii_lowercase().starts_with("application/json"));
+    let (html, options, rates) = if is_json {
+        let body: JsonBody = serde_json::from_slice(&request.body).map_err(|err| Failure(400, format!("invalid JSON body: {}", err)))?;
+        (body.html, body.options, body.rates)
+    } else {
+        (encoding::decode(&request.body, request.content_type.as_deref()), Options::default(), ReadingRates::default())
+    };
+
+    let endpoint = endpoint.to_string();
+    Ok(Box::new(move || match endpoint.as_str() {
+        "/clean" => json!({ "text": super::prepare_text_with_options(&html, &options) }),
+        "/segment" => json!({ "paragraphs": super::prepare_paragraphs(&html, &options) }),
+        "/metadata" => json!(super::extract_metadata(&html)),
+        _ => json!(super::statistics(&html, &options, &rates)),
+    }))
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: &Value) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::{serve, Config};
+
+    /// Gates released so far, jobs of `POST /test/block` wait for the gate named by their body
+    static RELEASED: (Mutex<Vec<String>>, Condvar) = (Mutex::new(vec![]), Condvar::new());
+
+    pub(super) fn wait_for_release(gate: &str) {
+        let (released, signal) = &RELEASED;
+        let mut released = released.lock().unwrap();
+        while !released.iter().any(|name| name == gate) {
+            released = signal.wait(released).unwrap();
+        }
+    }
+
+    fn release(gate: &str) {
+        let (released, signal) = &RELEASED;
+        released.lock().unwrap().push(gate.to_string());
+        signal.notify_all();
+    }
+
+    struct TestServer {
+        addr: SocketAddr,
+        shutdown: Arc<AtomicBool>,
+        handle: Option<thread::JoinHandle<()>>,
+    }
+
+    impl TestServer {
+        fn start(config: Config) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap();
+            let shutdown = Arc::new(AtomicBool::new(false));
+            let flag = Arc::clone(&shutdown);
+            let handle = thread::spawn(move || serve(listener, config, flag).unwrap());
+            TestServer { addr, shutdown, handle: Some(handle) }
+        }
+
+        fn raw(&self, request: &[u8]) -> (u16, Value) {
+            let mut stream = TcpStream::connect(self.addr).unwrap();
+            stream.write_all(request).unwrap();
+            read_response(stream)
+        }
+
+        fn post(&self, path: &str, content_type: &str, body: &[u8]) -> (u16, Value) {
+            let mut request = format!(
+                "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+                path,
+                content_type,
+                body.len()
+            )
+            .into_bytes();
+            request.extend_from_slice(body);
+            self.raw(&request)
+        }
+    }
+
+    impl Drop for TestServer {
+        fn drop(&mut self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+            if let Some(handle) = self.handle.take() {
+                handle.join().unwrap();
+            }
+        }
+    }
+
+    fn read_response(mut stream: TcpStream) -> (u16, Value) {
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn endpoints() {
+        let server = TestServer::start(Config::default());
+        let html = b"<html lang=\"en\"><head><title>Title</title></head><body><p>First sentence. Second one!</p></body></html>";
+
+        let (status, body) = server.raw(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert_eq!((200, "ok"), (status, body["status"].as_str().unwrap()));
+
+        let (status, body) = server.post("/clean", "text/html", html);
+        assert_eq!(200, status);
+        assert_eq!(crate::prepare_text(std::str::from_utf8(html).unwrap()), body["text"]);
+
+        let (status, body) = server.post("/segment", "text/html", b"<p>First sentence. Second one!</p><p>Third?</p>");
+        assert_eq!(200, status);
+        assert_eq!(serde_json::json!([["First sentence.", "Second one!"], ["Third?"]]), body["paragraphs"]);
+
+        let (status, body) = server.post("/metadata", "text/html", html);
+        assert_eq!((200, "en"), (status, body["language"].as_str().unwrap()));
+
+        let (status, body) = server.post("/stats", "text/html", b"<p>First sentence. Second one!</p>");
+        assert_eq!((200, 2), (status, body["sentences"].as_u64().unwrap()));
+
+        let (status, body) = server.post("/stats", "application/json", br#"{"html": "<p>Four words in here.</p>", "rates": {"reading": {"en": 2}}}"#);
+        assert_eq!((200, 2.0), (status, body["readingMinutes"].as_f64().unwrap()));
+    }
+
+    #[test]
+    fn json_body_and_charsets() {
+        let server = TestServer::start(Config::default());
+
+        let (status, body) = server.post("/clean", "application/json", br#"{"html": "<p>See here[1].</p>", "options": {"stripCitations": false}}"#);
+        assert_eq!((200, "See here[1]."), (status, body["text"].as_str().unwrap()));
+
+        let (status, body) = server.post("/clean", "text/html; charset=windows-1252", b"<p>Caf\xE9 cr\xE8me.</p>");
+        assert_eq!((200, "Café crème."), (status, body["text"].as_str().unwrap()));
+
+        let (status, _) = server.post("/clean", "application/json", br#"{"text": "<p>wrong field</p>"}"#);
+        assert_eq!(400, status);
+    }
+
+    #[test]
+    fn errors_and_limits() {
+        let server = TestServer::start(Config { max_body_bytes: 64, ..Config::default() });
+
+        assert_eq!(404, server.post("/nope", "text/html", b"").0);
+        assert_eq!(405, server.raw(b"GET /clean HTTP/1.1\r\n\r\n").0);
+        assert_eq!(411, server.raw(b"POST /clean HTTP/1.1\r\n\r\n").0);
+        assert_eq!(400, server.raw(b"NONSENSE\r\n\r\n").0);
+
+        let (status, body) = server.post("/clean", "text/html", &[b'a'; 65]);
+        assert_eq!(413, status);
+        assert_eq!("body exceeds 64 bytes", body["error"]);
+    }
+
+    #[test]
+    fn timeouts() {
+        let server = TestServer::start(Config {
+            io_timeout: Duration::from_millis(250),
+            processing_timeout: Duration::ZERO,
+            ..Config::default()
+        });
+
+        // headers never finish
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        stream.write_all(b"POST /clean HTTP/1.1\r\nContent-Length: 10\r\n").unwrap();
+        assert_eq!(408, read_response(stream).0);
+
+        // headers keep coming, but not within the time allowed for the whole request
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        stream.write_all(b"POST /clean HTTP/1.1\r\n").unwrap();
+        stream.set_read_timeout(Some(Duration::from_millis(100))).unwrap();
+        let mut writes = 0;
+        while stream.peek(&mut [0]).is_err() {
+            writes += 1;
+            assert!(writes < 20, "request deadline never passed");
+            stream.write_all(b"X-Slow: 1\r\n").unwrap();
+        }
+        stream.set_read_timeout(None).unwrap();
+        assert_eq!(408, read_response(stream).0);
+
+        assert_eq!(503, server.post("/test/block", "text/plain", b"timeouts").0);
+        release("timeouts");
+    }
+
+    #[test]
+    fn timed_out_jobs_keep_their_slot() {
+        let server = TestServer::start(Config { processing_timeout: Duration::ZERO, max_connections: 1, ..Config::default() });
+
+        assert_eq!(503, server.post("/test/block", "text/plain", b"slot").0);
+        let (status, body) = read_response(TcpStream::connect(server.addr).unwrap());
+        assert_eq!((503, "too many connections"), (status, body["error"].as_str().unwrap()));
+        release("slot");
+    }
+
+    #[test]
+    fn graceful_shutdown() {
+        let mut server = TestServer::start(Config::default());
+
+        // request in flight while shutting down still gets its answer
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        stream.write_all(b"GET /health HTTP/1.1\r\n").unwrap();
+        thread::sleep(Duration::from_millis(100));
+        server.shutdown.store(true, Ordering::SeqCst);
+        stream.write_all(b"\r\n").unwrap();
+        assert_eq!(200, read_response(stream).0);
+
+        server.handle.take().unwrap().join().unwrap();
+        assert!(TcpStream::connect(server.addr).is_err());
+    }
+}
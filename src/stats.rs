@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::language::language_code;
+use super::metrics;
+use super::Options;
+
+/// Size of a cleaned up document and the time it takes to read it
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    /// Primary language subtag the reading rates were chosen for, such as `en` or `zh`
+    pub language: String,
+    pub paragraphs: usize,
+    pub sentences: usize,
+    /// Words as counted by the readability metrics, every Chinese or Japanese character counts as a word
+    pub words: usize,
+    /// Unicode characters of the cleaned text, including separators
+    pub characters: usize,
+    pub characters_without_spaces: usize,
+    /// Words per sentence
+    pub average_sentence_length: f64,
+    /// Distinct words divided by words, case insensitive
+    pub lexical_diversity: f64,
+    pub reading_minutes: f64,
+    pub speaking_minutes: f64,
+}
+
+/// Reading and speaking rates in words per minute, Chinese and Japanese rates count characters.
+///
+/// Entries are keyed by primary language subtag and override the built-in rates: silent
+/// reading rates measured by Trauzettel-Klosinski and Dietz (2012) for 17 languages, their
+/// mean for other languages, and 150 words per minute when speaking.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReadingRates {
+    pub reading: HashMap<String, f64>,
+    pub speaking: HashMap<String, f64>,
+}
+
+impl ReadingRates {
+    /// Words per minute when reading `language` silently
+    pub fn reading(&self, language: &str) -> f64 {
+        if let Some(&rate) = self.reading.get(language) {
+            return rate;
+        }
+        match language {
+            "ar" => 138.0,
+            "zh" => 255.0,
+            "nl" => 202.0,
+            "en" => 228.0,
+            "fi" => 161.0,
+            "fr" => 195.0,
+            "de" => 179.0,
+            "he" => 187.0,
+            "it" => 188.0,
+            "ja" => 357.0,
+            "pl" => 166.0,
+            "pt" => 181.0,
+            "ru" => 184.0,
+            "sl" => 180.0,
+            "es" => 218.0,
+            "sv" => 199.0,
+            "tr" => 166.0,
+            _ => 184.0,
+        }
+    }
+
+    /// Words per minute when reading `language` aloud
+    pub fn speaking(&self, language: &str) -> f64 {
+        if let Some(&rate) = self.speaking.get(language) {
+            return rate;
+        }
+        match language {
+            "zh" => 240.0,
+            "ja" => 300.0,
+            _ => 150.0,
+        }
+    }
+}
+
+/// Cleans up `html` and measures the result, the language is the declared language
+/// of the document or the one detected in its text
+pub fn statistics(html: &str, options: &Options, rates: &ReadingRates) -> Stats {
+    let paragraphs = super::prepare_paragraphs(html, options);
+    let language = language_code(super::extract_metadata(html).language.as_deref(), &paragraphs);
+    Stats::of(&paragraphs, &language, rates)
+}
+
+impl Stats {
+    /// Measures paragraphs and sentences as produced by `prepare_paragraphs`
+    pub fn of(paragraphs: &[Vec<String>], language: &str, rates: &ReadingRates) -> Self {
+        let sentences = paragraphs.iter().flatten();
+        let sentence_count = paragraphs.iter().map(Vec::len).sum::<usize>();
+        let separators = sentence_count.saturating_sub(paragraphs.len()) + 2 * paragraphs.len().saturating_sub(1);
+
+        let mut words = 0;
+        let mut distinct = HashSet::new();
+        for word in sentences.clone().flat_map(|sentence| metrics::words(sentence)) {
+            words += 1;
+            distinct.insert(word.to_lowercase());
+        }
+        let ratio = |numerator: f64, denominator: usize| if denominator == 0 { 0.0 } else { numerator / denominator as f64 };
+
+        Stats {
+            language: language.to_string(),
+            paragraphs: paragraphs.len(),
+            sentences: sentence_count,
+            words,
+            characters: sentences.clone().map(|sentence| sentence.chars().count()).sum::<usize>() + separators,
+            characters_without_spaces: sentences.map(|sentence| sentence.chars().filter(|ch| !ch.is_whitespace()).count()).sum(),
+            average_sentence_length: ratio(words as f64, sentence_count),
+            lexical_diversity: ratio(distinct.len() as f64, words),
+            reading_minutes: words as f64 / rates.reading(language),
+            speaking_minutes: words as f64 / rates.speaking(language),
+        }
+    }
+}
+
+/// Chinese characters and Japanese kana, written without spaces between words
+pub(crate) fn is_cjk(ch: char) -> bool {
+    matches!(ch,
+        '\u{3040}'..='\u{30FF}' | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FA1F}')
+}
+
+#[cfg(test)]
+mod test {
+    use super::{statistics, ReadingRates, Stats};
+    use crate::Options;
+
+    #[test]
+    fn counts() {
+        let paragraphs = vec![
+            vec!["First sentence here.".to_string(), "Second – one!".to_string()],
+            vec!["Café? Second".to_string()],
+        ];
+        let text = "First sentence here. Second – one!\n\nCafé? Second";
+
+        let stats = Stats::of(&paragraphs, "en", &ReadingRates::default());
+        assert_eq!(2, stats.paragraphs);
+        assert_eq!(3, stats.sentences);
+        assert_eq!(7, stats.words);
+        assert_eq!(text.chars().count(), stats.characters);
+        assert_eq!(text.chars().filter(|ch| !ch.is_whitespace()).count(), stats.characters_without_spaces);
+        assert_eq!(7.0 / 3.0, stats.average_sentence_length);
+        assert_eq!(6.0 / 7.0, stats.lexical_diversity);
+        assert_eq!(7.0 / 228.0, stats.reading_minutes);
+        assert_eq!(7.0 / 150.0, stats.speaking_minutes);
+        assert_eq!(Stats { language: "en".to_string(), ..Stats::default() }, Stats::of(&[], "en", &ReadingRates::default()));
+    }
+
+    #[test]
+    fn cjk_and_rates() {
+        let stats = statistics("<p>東京は大きい都市です。</p>", &Options::default(), &ReadingRates::default());
+        assert_eq!("ja", stats.language);
+        assert_eq!(10, stats.words);
+        assert_eq!(10.0 / 357.0, stats.reading_minutes);
+
+        let rates: ReadingRates = serde_json::from_str(r#"{"reading": {"de": 100}}"#).unwrap();
+        let stats = statistics("<html lang=\"de-CH\"><p>Ein kurzer Satz.</p></html>", &Options::default(), &rates);
+        assert_eq!("de", stats.language);
+        assert_eq!(3.0 / 100.0, stats.reading_minutes);
+        assert_eq!(3.0 / 150.0, stats.speaking_minutes);
+        assert_eq!(228.0, rates.reading("en"));
+    }
+}
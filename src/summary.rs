@@ -0,0 +1,285 @@
+//! Extractive summaries: ranks the sentences of the cleaned text with TextRank or LexRank
+//! and keeps the best ones in document order.
+//!
+//! Results only depend on the input, ties are broken by sentence position.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::language::document_language;
+use super::metrics::words;
+use super::{Language, Options};
+
+const DAMPING: f64 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-9;
+
+/// Graph ranking of the sentences
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Algorithm {
+    /// Shared words normalized by sentence lengths (Mihalcea & Tarau)
+    #[default]
+    TextRank,
+    /// Cosine similarity of TF-IDF vectors (Erkan & Radev)
+    LexRank,
+}
+
+/// Size of the summary, as sentences or as an upper bound in words or characters
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Length {
+    Sentences(usize),
+    Words(usize),
+    Characters(usize),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SummaryOptions {
+    pub algorithm: Algorithm,
+    pub length: Length,
+    /// Weight of the boost given to early sentences, 0 disables it
+    pub position_boost: f64,
+    /// Weight of the boost given to sentences sharing words with the title, 0 disables it
+    pub title_boost: f64,
+}
+
+impl Default for SummaryOptions {
+    fn default() -> Self {
+        SummaryOptions {
+            algorithm: Algorithm::TextRank,
+            length: Length::Sentences(3),
+            position_boost: 0.2,
+            title_boost: 0.3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummarySentence {
+    /// Position of the sentence among all sentences of the cleaned text
+    pub index: usize,
+    pub text: String,
+    pub score: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    /// The selected sentences joined with spaces
+    pub text: String,
+    /// The selected sentences in document order
+    pub sentences: Vec<SummarySentence>,
+}
+
+/// Cleans up `html` and extracts its most representative sentences
+pub fn summarize(html: &str, options: &Options, summary: &SummaryOptions) -> Summary {
+    let paragraphs = super::prepare_paragraphs(html, options);
+    let language = document_language(html, &paragraphs);
+    let title = super::extract_metadata(html).title.unwrap_or_default();
+
+    // html2md keeps the `<title>`, it's no summary material
+    let sentences: Vec<&String> = paragraphs.iter().flatten().filter(|sentence| sentence.trim() != title).collect();
+    let terms: Vec<Vec<String>> = sentences.iter().map(|sentence| content_words(sentence, language)).collect();
+    let title_terms: BTreeSet<String> = content_words(&title, language).into_iter().collect();
+
+    let weights = match summary.algorithm {
+        Algorithm::TextRank => text_rank_weights(&terms),
+        Algorithm::LexRank => lex_rank_weights(&terms),
+    };
+    let ranks = page_rank(&weights);
+
+    let count = sentences.len();
+    let scores: Vec<f64> = ranks
+        .iter()
+        .enumerate()
+        .map(|(index, rank)| {
+            let position = (count - index) as f64 / count as f64;
+            let overlap = if title_terms.is_empty() {
+                0.0
+            } else {
+                terms[index].iter().collect::<BTreeSet<_>>().iter().filter(|term| title_terms.contains(term.as_str())).count() as f64
+                    / title_terms.len() as f64
+            };
+            rank * (1.0 + summary.position_boost * position + summary.title_boost * overlap)
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..count).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+
+    let mut selected = vec![];
+    let (mut words_used, mut characters_used) = (0, 0);
+    for index in order {
+        let sentence = sentences[index];
+        let fits = match summary.length {
+            Length::Sentences(limit) => selected.len() < limit,
+            Length::Words(limit) => words_used + words(sentence).count() <= limit,
+            Length::Characters(limit) => characters_used + usize::from(!selected.is_empty()) + sentence.chars().count() <= limit,
+        };
+        if fits {
+            words_used += words(sentence).count();
+            characters_used += usize::from(!selected.is_empty()) + sentence.chars().count();
+            selected.push(index);
+        }
+    }
+    selected.sort_unstable();
+
+    let sentences: Vec<SummarySentence> = selected
+        .into_iter()
+        .map(|index| SummarySentence { index, text: sentences[index].clone(), score: scores[index] })
+        .collect();
+    Summary {
+        text: sentences.iter().map(|sentence| sentence.text.as_str()).collect::<Vec<_>>().join(" "),
+        sentences,
+    }
+}
+
+/// Lowercased words of `text` without function words
+fn content_words(text: &str, language: Language) -> Vec<String> {
+    words(text)
+        .map(str::to_lowercase)
+        .filter(|word| !language.is_function_word(word))
+        .collect()
+}
+
+fn text_rank_weights(terms: &[Vec<String>]) -> Vec<Vec<f64>> {
+    let sets: Vec<BTreeSet<&String>> = terms.iter().map(|terms| terms.iter().collect()).collect();
+    similarity_matrix(terms.len(), |a, b| {
+        let shared = sets[a].intersection(&sets[b]).count() as f64;
+        let norm = (terms[a].len() as f64).ln() + (terms[b].len() as f64).ln();
+        if shared == 0.0 || norm <= 0.0 {
+            0.0
+        } else {
+            shared / norm
+        }
+    })
+}
+
+fn lex_rank_weights(terms: &[Vec<String>]) -> Vec<Vec<f64>> {
+    let mut document_frequency: BTreeMap<&String, usize> = BTreeMap::new();
+    for sentence in terms {
+        for term in sentence.iter().collect::<BTreeSet<_>>() {
+            *document_frequency.entry(term).or_default() += 1;
+        }
+    }
+
+    let count = terms.len() as f64;
+    let vectors: Vec<BTreeMap<&String, f64>> = terms
+        .iter()
+        .map(|sentence| {
+            let mut vector: BTreeMap<&String, f64> = BTreeMap::new();
+            for term in sentence {
+                *vector.entry(term).or_default() += 1.0;
+            }
+            for (term, weight) in vector.iter_mut() {
+                *weight *= (count / document_frequency[term] as f64).ln() + 1.0;
+            }
+            vector
+        })
+        .collect();
+    let norms: Vec<f64> = vectors.iter().map(|vector| vector.values().map(|weight| weight * weight).sum::<f64>().sqrt()).collect();
+
+    similarity_matrix(terms.len(), |a, b| {
+        if norms[a] == 0.0 || norms[b] == 0.0 {
+            return 0.0;
+        }
+        let dot: f64 = vectors[a].iter().filter_map(|(term, weight)| vectors[b].get(term).map(|other| weight * other)).sum();
+        dot / (norms[a] * norms[b])
+    })
+}
+
+/// Symmetric matrix of `similarity` between all pairs of distinct sentences
+fn similarity_matrix(count: usize, similarity: impl Fn(usize, usize) -> f64) -> Vec<Vec<f64>> {
+    let mut matrix = vec![vec![0.0; count]; count];
+    for (a, b) in (0..count).flat_map(|a| (a + 1..count).map(move |b| (a, b))) {
+        let weight = similarity(a, b);
+        matrix[a][b] = weight;
+        matrix[b][a] = weight;
+    }
+    matrix
+}
+
+/// Weighted PageRank, sentences without edges spread their rank evenly
+fn page_rank(weights: &[Vec<f64>]) -> Vec<f64> {
+    let count = weights.len();
+    if count == 0 {
+        return vec![];
+    }
+    let totals: Vec<f64> = weights.iter().map(|row| row.iter().sum()).collect();
+    let mut ranks = vec![1.0 / count as f64; count];
+
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = (0..count).filter(|&index| totals[index] == 0.0).map(|index| ranks[index]).sum::<f64>() / count as f64;
+        let next: Vec<f64> = (0..count)
+            .map(|target| {
+                let incoming: f64 = (0..count)
+                    .filter(|&source| totals[source] > 0.0)
+                    .map(|source| ranks[source] * weights[source][target] / totals[source])
+                    .sum();
+                (1.0 - DAMPING) / count as f64 + DAMPING * (incoming + dangling)
+            })
+            .collect();
+        let change: f64 = next.iter().zip(&ranks).map(|(next, rank)| (next - rank).abs()).sum();
+        ranks = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod test {
+    use super::{summarize, Algorithm, Length, SummaryOptions};
+    use crate::Options;
+
+    const ARTICLE: &str = "<html><head><title>Solar power growth</title></head><body>
+        <p>Solar power capacity grew quickly across Europe last year. Installers reported record demand for rooftop panels.</p>
+        <p>The weather was mild in many regions. Some farmers planted crops earlier than usual.</p>
+        <p>Analysts expect solar power capacity to keep growing as panel prices fall. Rooftop panels now pay for themselves faster.</p>
+        </body></html>";
+
+    #[test]
+    fn selects_central_sentences_in_order() {
+        for algorithm in [Algorithm::TextRank, Algorithm::LexRank] {
+            let options = SummaryOptions { algorithm, length: Length::Sentences(2), ..SummaryOptions::default() };
+            let summary = summarize(ARTICLE, &Options::default(), &options);
+
+            assert_eq!(2, summary.sentences.len(), "{:?}", algorithm);
+            assert!(summary.sentences[0].index < summary.sentences[1].index);
+            assert!(summary.text.contains("Solar power capacity grew quickly"), "{:?}: {}", algorithm, summary.text);
+            assert!(!summary.text.contains("weather"), "{:?}: {}", algorithm, summary.text);
+            assert_eq!(summary, summarize(ARTICLE, &Options::default(), &options));
+        }
+    }
+
+    #[test]
+    fn fixture_snapshot() {
+        let html = std::fs::read_to_string("tests/fixtures/article.html").unwrap();
+        let options = SummaryOptions { length: Length::Sentences(2), ..SummaryOptions::default() };
+        assert_eq!(
+            "Healthy fast food isn't what it seems Proponents have dubbed them “healthy fast food”, eg the salads at Sweetgreen.",
+            summarize(&html, &Options::default(), &options).text
+        );
+    }
+
+    #[test]
+    fn length_limits() {
+        let by_words = summarize(ARTICLE, &Options::default(), &SummaryOptions { length: Length::Words(20), ..SummaryOptions::default() });
+        assert!(!by_words.sentences.is_empty());
+        assert!(crate::metrics::words(&by_words.text).count() <= 20);
+
+        let by_characters =
+            summarize(ARTICLE, &Options::default(), &SummaryOptions { length: Length::Characters(80), ..SummaryOptions::default() });
+        assert!(!by_characters.sentences.is_empty());
+        assert!(by_characters.text.chars().count() <= 80);
+
+        let options: SummaryOptions = serde_json::from_str(r#"{"algorithm": "lexRank", "length": {"sentences": 1}, "titleBoost": 0}"#).unwrap();
+        assert_eq!(1, summarize(ARTICLE, &Options::default(), &options).sentences.len());
+        assert!(summarize("", &Options::default(), &options).sentences.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use html2md::common::{get_tag_attr, tag_name};
+use html2md::tables::{GridCell, TableGrid};
+use markup5ever_rcdom::{Handle, NodeData};
+use serde::Serialize;
+
+use super::dom::{descendants, parse_dom};
+
+/// Data table found in the HTML markup. Spans are expanded: the text of a cell
+/// is repeated in every slot it covers, so all rows have the same length
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    pub caption: Option<String>,
+    /// Rows of `thead`, or the leading rows with `th` cells only
+    pub header: Vec<Vec<String>>,
+    /// Rows of `tbody`, then rows of `tfoot`
+    pub body: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// RFC 4180 CSV of the header and body rows, the caption is left out
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in self.header.iter().chain(&self.body) {
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+}
+
+/// Extracts every data table of `html`, nested ones included, in document order.
+/// Layout tables marked with `role="presentation"` and tables without cells are skipped
+pub fn extract_tables(html: &str) -> Vec<Table> {
+    let dom = parse_dom(html);
+
+    descendants(&dom.document)
+        .filter(|node| {
+            let role = get_tag_attr(node, "role").map(|role| role.to_lowercase());
+            tag_name(node) == "table" && !matches!(role.as_deref(), Some("presentation" | "none"))
+        })
+        .map(|node| TableGrid::new(&node))
+        .filter(|grid| grid.columns > 0)
+        .map(|grid| Table {
+            caption: grid.caption.as_ref().map(cell_text).filter(|caption| !caption.is_empty()),
+            header: grid.head.iter().map(|row| row_texts(row)).collect(),
+            body: grid.body.iter().map(|row| row_texts(row)).collect(),
+        })
+        .collect()
+}
+
+fn row_texts(row: &[Option<GridCell>]) -> Vec<String> {
+    row.iter().map(|slot| slot.as_ref().map(|cell| cell_text(&cell.handle)).unwrap_or_default()).collect()
+}
+
+/// Text of the cell with whitespace collapsed, nested tables excluded
+fn cell_text(cell: &Handle) -> String {
+    fn gather(node: &Handle, text: &mut String) {
+        match node.data {
+            NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+            NodeData::Element { ref name, .. } => match name.local.as_ref() {
+                "script" | "style" | "table" => {}
+                "br" => text.push(' '),
+                local => {
+                    let block = matches!(local, "p" | "div" | "li" | "ul" | "ol" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6");
+                    if block {
+                        text.push(' ');
+                    }
+                    for child in node.children.borrow().iter() {
+                        gather(child, text);
+                    }
+                    if block {
+                        text.push(' ');
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    let mut text = String::new();
+    for child in cell.children.borrow().iter() {
+        gather(child, &mut text);
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Quotes the field when it contains a comma, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{extract_tables, Table};
+
+    #[test]
+    fn spans_and_row_groups() {
+        let tables = extract_tables(
+            r#"<table>
+                <caption>Results</caption>
+                <tfoot><tr><td>Total</td><td colspan="2">7</td></tr></tfoot>
+                <thead><tr><th rowspan="2">Team</th><th colspan="2">Score</th></tr><tr><th>Home</th><th>Away</th></tr></thead>
+                <tbody><tr><td rowspan="2">Lions</td><td>1</td><td>2<br>(a.e.t.)</td></tr><tr><td>3</td><td>1</td></tr></tbody>
+            </table>"#,
+        );
+        let rows = |rows: &[&[&str]]| rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect();
+        assert_eq!(
+            vec![Table {
+                caption: Some("Results".to_string()),
+                header: rows(&[&["Team", "Score", "Score"], &["Team", "Home", "Away"]]),
+                body: rows(&[&["Lions", "1", "2 (a.e.t.)"], &["Lions", "3", "1"], &["Total", "7", "7"]]),
+            }],
+            tables
+        );
+    }
+
+    #[test]
+    fn nested_and_layout_tables() {
+        let tables = extract_tables(
+            r#"<table role="presentation"><tr><td>
+                <table><tr><td>Outer<table><tr><td>Inner</td></tr></table></td></tr></table>
+                <table><tr></tr></table>
+            </td></tr></table>"#,
+        );
+        let bodies: Vec<&Vec<Vec<String>>> = tables.iter().map(|table| &table.body).collect();
+        assert_eq!(vec![&vec![vec!["Outer".to_string()]], &vec![vec!["Inner".to_string()]]], bodies);
+    }
+
+    #[test]
+    fn csv() {
+        let table = Table {
+            caption: None,
+            header: vec![vec!["Name".to_string(), "Quote".to_string()]],
+            body: vec![vec!["Smith, J.".to_string(), "Say \"cheese\"".to_string()], vec!["Doe".to_string(), String::new()]],
+        };
+        assert_eq!("Name,Quote\r\n\"Smith, J.\",\"Say \"\"cheese\"\"\"\r\nDoe,\r\n", table.to_csv());
+    }
+}
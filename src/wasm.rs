@@ -0,0 +1,473 @@
+//! JavaScript interface returning structured results as plain JS objects.
+//!
+//! Offsets in the returned objects are UTF-16 code unit offsets, so that
+//! `document.text.slice(sentence.start, sentence.end)` works in JavaScript.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use super::document::Document;
+use super::{AnalysisSettings, KeywordOptions, Options, ReadingRates, SummaryOptions, Table};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &'static str = r#"
+/** Switches for the optional steps of the cleanup pipeline, defaults are those of `prepareText` */
+export interface Options {
+    /** Rewrite common abbreviations ("e.g.", "Dr.") so that their dots don't end sentences, true by default */
+    replaceAbbreviations?: boolean;
+    /** Remove numeric citation markers such as `[12]`, true by default */
+    stripCitations?: boolean;
+    /** Leave out the content of `nav`, `aside`, `form` and `footer` elements, false by default */
+    dropBoilerplate?: boolean;
+    /** Keep the captions of figures and tables, as paragraphs of their own, true by default */
+    captions?: boolean;
+    /** Token written in place of every formula, such as `[MATH]`, formulas are written as linear text otherwise */
+    mathPlaceholder?: string | null;
+    /** Text kept for ruby annotations: the base text (default), the base text followed by its reading in parentheses, or the reading */
+    ruby?: "base" | "parenthesized" | "reading";
+}
+
+/** A sentence of the cleaned text, `text === document.text.slice(start, end)` */
+export interface Sentence {
+    text: string;
+    start: number;
+    end: number;
+}
+
+export interface Paragraph {
+    start: number;
+    end: number;
+    sentences: Sentence[];
+}
+
+export interface Heading {
+    level: 1 | 2 | 3 | 4 | 5 | 6;
+    text: string;
+}
+
+export interface Metadata {
+    title: string | null;
+    byline: string | null;
+    description: string | null;
+    language: string | null;
+    siteName: string | null;
+    published: string | null;
+    url: string | null;
+    keywords: string[];
+    headings: Heading[];
+}
+
+export interface Stats {
+    /** Primary language subtag the reading rates were chosen for */
+    language: string;
+    paragraphs: number;
+    sentences: number;
+    /** Chinese and Japanese characters count as words */
+    words: number;
+    characters: number;
+    charactersWithoutSpaces: number;
+    /** Words per sentence */
+    averageSentenceLength: number;
+    /** Distinct words divided by words */
+    lexicalDiversity: number;
+    readingMinutes: number;
+    speakingMinutes: number;
+}
+
+/** Words per minute by primary language subtag, overriding the built-in rates */
+export interface ReadingRates {
+    reading?: Record<string, number>;
+    speaking?: Record<string, number>;
+}
+
+/** Counts the readability formulas are based on */
+export interface TextStats {
+    sentences: number;
+    words: number;
+    syllables: number;
+    letters: number;
+    /** Words of one syllable */
+    monosyllables: number;
+    /** Words of three or more syllables */
+    polysyllables: number;
+    /** Polysyllables except proper nouns and inflected forms, as defined by Gunning */
+    complexWords: number;
+    /** Words of more than six letters */
+    longWords: number;
+    /** Words missing from the Dale–Chall list of familiar words */
+    difficultWords: number;
+}
+
+/** Readability formulas made for languages other than English, LIX and RIX apply to all */
+export type Formula = "amstad" | "wienerSachtextformel" | "kandelMoles" | "fernandezHuerta" | "szigrisztPazos" | "gulpease" | "lix" | "rix";
+
+export interface Score {
+    formula: Formula;
+    value: number;
+}
+
+/** Readability scores, all English ones but `fleschReadingEase` and `daleChall` are US school grade levels */
+export interface Readability {
+    /** Declared or detected language the text was analysed as */
+    language: "en" | "de" | "fr" | "es" | "it" | "sv";
+    stats: TextStats;
+    fleschReadingEase: number;
+    fleschKincaidGrade: number;
+    gunningFog: number;
+    smog: number;
+    automatedReadabilityIndex: number;
+    colemanLiau: number;
+    daleChall: number;
+    /** Scores of the formulas made for `language` */
+    languageScores: Score[];
+}
+
+/** Thresholds and word lists of `analyze`, matched case insensitively */
+export interface AnalysisSettings {
+    /** Sentences with more words are very long, 25 by default */
+    veryLongSentenceWords?: number;
+    /** Words ending in `-ly` that are not adverbs */
+    adverbExceptions?: string[];
+    /** Hedging words and phrases */
+    qualifiers?: string[];
+    /** Words and phrases mapped to a simpler alternative */
+    simplerAlternatives?: Record<string, string>;
+}
+
+export type Category = "veryLongSentence" | "passive" | "adverb" | "qualifier" | "simplerAlternative";
+
+/** A span of `analysis.text` with a writing issue */
+export interface Annotation {
+    category: Category;
+    start: number;
+    end: number;
+    /** Replacement for `simplerAlternative` annotations */
+    suggestion: string | null;
+}
+
+export interface AnnotatedSentence {
+    start: number;
+    end: number;
+    words: number;
+    annotations: Annotation[];
+}
+
+export interface Analysis {
+    text: string;
+    sentences: AnnotatedSentence[];
+}
+
+/** Summary size as a number of sentences or an upper bound of words or characters */
+export type SummaryLength = { sentences: number } | { words: number } | { characters: number };
+
+export interface SummaryOptions {
+    /** `textRank` by default */
+    algorithm?: "textRank" | "lexRank";
+    /** Three sentences by default */
+    length?: SummaryLength;
+    /** Weight of the boost given to early sentences, 0 disables it */
+    positionBoost?: number;
+    /** Weight of the boost given to sentences sharing words with the title, 0 disables it */
+    titleBoost?: number;
+}
+
+export interface SummarySentence {
+    /** Position among all sentences of the cleaned text */
+    index: number;
+    text: string;
+    score: number;
+}
+
+export interface Summary {
+    /** The selected sentences joined with spaces */
+    text: string;
+    /** The selected sentences in document order */
+    sentences: SummarySentence[];
+}
+
+export interface KeywordOptions {
+    /** `rake` by default */
+    algorithm?: "rake" | "yake";
+    /** Maximum number of keyphrases returned, 10 by default */
+    limit?: number;
+    /** Longest keyphrase in words, 3 by default */
+    maxWords?: number;
+    /** Weight of the boost given to phrases sharing words with the title and headings, 0 disables it */
+    headingBoost?: number;
+}
+
+/** UTF-16 offsets into the cleaned text */
+export interface Occurrence {
+    start: number;
+    end: number;
+}
+
+export interface Keyword {
+    /** Lowercased words of the phrase separated by spaces */
+    phrase: string;
+    /** Relevance, higher is better */
+    score: number;
+    occurrences: Occurrence[];
+}
+
+export interface Table {
+    caption: string | null;
+    /** Header rows, a cell spanning several columns or rows is repeated in each of them */
+    header: string[][];
+    /** Body rows then footer rows, spans expanded the same way */
+    body: string[][];
+}
+
+export interface Image {
+    /** Largest `srcset` candidate of the image and its `picture` sources, then `data-src`, `data-lazy-src`, then `src` */
+    url: string;
+    alt: string | null;
+    /** Caption of the enclosing `figure` */
+    caption: string | null;
+    width: number | null;
+    height: number | null;
+    /** Whether this is the lead image of the page, for article cards */
+    lead: boolean;
+}
+
+export interface Link {
+    /** Anchor text, or the alt text of the image the link wraps */
+    text: string;
+    /** URL resolved against the `<base>` of the page and the page URL, as written when neither is known */
+    url: string;
+    /** Lowercased `rel` keywords */
+    rel: string[];
+    /** Whether the link is in the main content rather than in navigation, sidebars, footer or forms */
+    inContent: boolean;
+}
+
+export interface CleanedDocument {
+    /** Same as the result of `prepareTextWithOptions` */
+    text: string;
+    paragraphs: Paragraph[];
+    metadata: Metadata;
+    stats: Stats;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Options")]
+    pub type JsOptions;
+
+    #[wasm_bindgen(typescript_type = "CleanedDocument")]
+    pub type JsCleanedDocument;
+
+    #[wasm_bindgen(typescript_type = "Metadata")]
+    pub type JsMetadata;
+
+    #[wasm_bindgen(typescript_type = "AnalysisSettings")]
+    pub type JsAnalysisSettings;
+
+    #[wasm_bindgen(typescript_type = "Analysis")]
+    pub type JsAnalysis;
+
+    #[wasm_bindgen(typescript_type = "SummaryOptions")]
+    pub type JsSummaryOptions;
+
+    #[wasm_bindgen(typescript_type = "Summary")]
+    pub type JsSummary;
+
+    #[wasm_bindgen(typescript_type = "KeywordOptions")]
+    pub type JsKeywordOptions;
+
+    #[wasm_bindgen(typescript_type = "Keyword[]")]
+    pub type JsKeywords;
+
+    #[wasm_bindgen(typescript_type = "ReadingRates")]
+    pub type JsReadingRates;
+
+    #[wasm_bindgen(typescript_type = "Stats")]
+    pub type JsStats;
+
+    #[wasm_bindgen(typescript_type = "Readability")]
+    pub type JsReadability;
+
+    #[wasm_bindgen(typescript_type = "string[][]")]
+    pub type JsParagraphs;
+
+    #[wasm_bindgen(typescript_type = "Table[]")]
+    pub type JsTables;
+
+    #[wasm_bindgen(typescript_type = "Image[]")]
+    pub type JsImages;
+
+    #[wasm_bindgen(typescript_type = "Link[]")]
+    pub type JsLinks;
+}
+
+/// Cleans up `html` and returns paragraphs, sentences with offsets, metadata and statistics
+#[wasm_bindgen(js_name = prepareDocument)]
+pub fn prepare_document(html: &str, options: Option<JsOptions>) -> Result<JsCleanedDocument, JsError> {
+    let mut document = super::prepare_document(html, &to_options(options)?);
+    to_utf16_offsets(&mut document);
+    Ok(to_js(&document)?.unchecked_into())
+}
+
+/// Same as `prepare_text` with explicit pipeline `options`
+#[wasm_bindgen(js_name = prepareTextWithOptions)]
+pub fn prepare_text_with_options(html: &str, options: Option<JsOptions>) -> Result<String, JsError> {
+    Ok(super::prepare_text_with_options(html, &to_options(options)?))
+}
+
+/// Cleans up `html` and returns its paragraphs as arrays of sentences
+#[wasm_bindgen(js_name = splitSentences)]
+pub fn split_sentences(html: &str, options: Option<JsOptions>) -> Result<JsParagraphs, JsError> {
+    let paragraphs = super::prepare_paragraphs(html, &to_options(options)?);
+    Ok(to_js(&paragraphs)?.unchecked_into())
+}
+
+/// Extracts title, byline, language and other document level information from `html`
+#[wasm_bindgen(js_name = extractMetadata)]
+pub fn extract_metadata(html: &str) -> Result<JsMetadata, JsError> {
+    Ok(to_js(&super::extract_metadata(html))?.unchecked_into())
+}
+
+/// Cleans up `html` and scores the readability of the result
+#[wasm_bindgen(js_name = readability)]
+pub fn readability(html: &str, options: Option<JsOptions>) -> Result<JsReadability, JsError> {
+    Ok(to_js(&super::readability(html, &to_options(options)?))?.unchecked_into())
+}
+
+/// Cleans up `html` and measures the result, reading times use `rates` where given
+#[wasm_bindgen(js_name = statistics)]
+pub fn statistics(html: &str, options: Option<JsOptions>, rates: Option<JsReadingRates>) -> Result<JsStats, JsError> {
+    let rates: ReadingRates = match rates {
+        Some(rates) if !rates.is_undefined() && !rates.is_null() => serde_wasm_bindgen::from_value(rates.into())?,
+        _ => ReadingRates::default(),
+    };
+    Ok(to_js(&super::statistics(html, &to_options(options)?, &rates))?.unchecked_into())
+}
+
+/// Cleans up `html` and annotates very long sentences, passive voice, adverbs,
+/// qualifiers and words with simpler alternatives
+#[wasm_bindgen(js_name = analyze)]
+pub fn analyze(html: &str, options: Option<JsOptions>, settings: Option<JsAnalysisSettings>) -> Result<JsAnalysis, JsError> {
+    let settings: AnalysisSettings = match settings {
+        Some(settings) if !settings.is_undefined() && !settings.is_null() => serde_wasm_bindgen::from_value(settings.into())?,
+        _ => AnalysisSettings::default(),
+    };
+    let mut analysis = super::analyze(html, &to_options(options)?, &settings);
+
+    let offsets = utf16_offsets(&analysis.text);
+    for sentence in &mut analysis.sentences {
+        sentence.start = offsets[sentence.start];
+        sentence.end = offsets[sentence.end];
+        for annotation in &mut sentence.annotations {
+            annotation.start = offsets[annotation.start];
+            annotation.end = offsets[annotation.end];
+        }
+    }
+    Ok(to_js(&analysis)?.unchecked_into())
+}
+
+/// Cleans up `html` and extracts its most representative sentences
+#[wasm_bindgen(js_name = summarize)]
+pub fn summarize(html: &str, options: Option<JsOptions>, summary: Option<JsSummaryOptions>) -> Result<JsSummary, JsError> {
+    let summary: SummaryOptions = match summary {
+        Some(summary) if !summary.is_undefined() && !summary.is_null() => serde_wasm_bindgen::from_value(summary.into())?,
+        _ => SummaryOptions::default(),
+    };
+    Ok(to_js(&super::summarize(html, &to_options(options)?, &summary))?.unchecked_into())
+}
+
+/// Cleans up `html` and extracts its highest scoring keyphrases
+#[wasm_bindgen(js_name = keywords)]
+pub fn keywords(html: &str, options: Option<JsOptions>, settings: Option<JsKeywordOptions>) -> Result<JsKeywords, JsError> {
+    let settings: KeywordOptions = match settings {
+        Some(settings) if !settings.is_undefined() && !settings.is_null() => serde_wasm_bindgen::from_value(settings.into())?,
+        _ => KeywordOptions::default(),
+    };
+    let options = to_options(options)?;
+    let mut keywords = super::keywords(html, &options, &settings);
+
+    let offsets = utf16_offsets(&super::prepare_text_with_options(html, &options));
+    for occurrence in keywords.iter_mut().flat_map(|keyword| &mut keyword.occurrences) {
+        occurrence.start = offsets[occurrence.start];
+        occurrence.end = offsets[occurrence.end];
+    }
+    Ok(to_js(&keywords)?.unchecked_into())
+}
+
+/// Extracts the data tables of `html`, nested ones included, in document order
+#[wasm_bindgen(js_name = extractTables)]
+pub fn extract_tables(html: &str) -> Result<JsTables, JsError> {
+    Ok(to_js(&super::extract_tables(html))?.unchecked_into())
+}
+
+/// Same as `extractTables` but returns every table as RFC 4180 CSV
+#[wasm_bindgen(js_name = extractTablesCsv)]
+pub fn extract_tables_csv(html: &str) -> Vec<String> {
+    super::extract_tables(html).iter().map(Table::to_csv).collect()
+}
+
+/// Extracts the content images of `html` in document order, without tracking pixels and spacers
+#[wasm_bindgen(js_name = extractImages)]
+pub fn extract_images(html: &str) -> Result<JsImages, JsError> {
+    Ok(to_js(&super::extract_images(html))?.unchecked_into())
+}
+
+/// Extracts the links of `html` in document order, resolving them against `base`, the URL of the page
+#[wasm_bindgen(js_name = extractLinks)]
+pub fn extract_links(html: &str, base: Option<String>) -> Result<JsLinks, JsError> {
+    Ok(to_js(&super::extract_links(html, base.as_deref()))?.unchecked_into())
+}
+
+fn to_options(options: Option<JsOptions>) -> Result<Options, JsError> {
+    match options {
+        Some(options) if !options.is_undefined() && !options.is_null() => Ok(serde_wasm_bindgen::from_value(options.into())?),
+        _ => Ok(Options::default()),
+    }
+}
+
+/// Converts to plain JS objects, `None` becomes `null`
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+/// UTF-16 code unit offset of every character offset of `text`, including its end
+fn utf16_offsets(text: &str) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    let mut utf16 = 0;
+    for ch in text.chars() {
+        offsets.push(utf16);
+        utf16 += ch.len_utf16();
+    }
+    offsets.push(utf16);
+    offsets
+}
+
+/// Rewrites the character offsets of `document` into UTF-16 code unit offsets
+fn to_utf16_offsets(document: &mut Document) {
+    let offsets = utf16_offsets(&document.text);
+    for paragraph in &mut document.paragraphs {
+        paragraph.start = offsets[paragraph.start];
+        paragraph.end = offsets[paragraph.end];
+        for sentence in &mut paragraph.sentences {
+            sentence.start = offsets[sentence.start];
+            sentence.end = offsets[sentence.end];
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{prepare_document, Options};
+
+    #[test]
+    fn utf16_offsets() {
+        let mut document = prepare_document("<p>Ünïcode 😀 first. Then second.</p>", &Options::default());
+        super::to_utf16_offsets(&mut document);
+
+        let utf16: Vec<u16> = document.text.encode_utf16().collect();
+        for sentence in &document.paragraphs[0].sentences {
+            assert_eq!(sentence.text, String::from_utf16(&utf16[sentence.start..sentence.end]).unwrap());
+        }
+        assert_eq!(utf16.len(), document.paragraphs[0].end);
+    }
+}
@@ -0,0 +1,89 @@
+/* Exercises the C interface, run with `make ctest` */
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "readability_text_cleanup.h"
+
+static void test_prepare_text(void) {
+    char *out = NULL;
+    RtcStatus status = rtc_prepare_text("<p>First sentence. Second one, e.g. this.</p><p>See here[1].</p>", NULL, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "First sentence. Second one, eg this.\n\nSee here.") == 0);
+    assert(rtc_last_error_message() == NULL);
+    rtc_string_free(out);
+}
+
+static void test_options(void) {
+    RtcOptions options = rtc_options_default();
+    assert(options.replace_abbreviations && options.strip_citations && !options.drop_boilerplate && options.captions);
+    assert(options.math_placeholder == NULL && options.ruby == RTC_RUBY_BASE);
+
+    options.replace_abbreviations = false;
+    options.strip_citations = false;
+
+    char *out = NULL;
+    RtcStatus status = rtc_prepare_text("<p>See here[1].</p>", &options, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "See here[1].") == 0);
+    rtc_string_free(out);
+
+    options.drop_boilerplate = true;
+    status = rtc_prepare_text("<nav>Home</nav><p>Text.</p>", &options, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "Text.") == 0);
+    rtc_string_free(out);
+
+    options.captions = false;
+    status = rtc_prepare_text("<figure><figcaption>Sales.</figcaption></figure><p>Text.</p>", &options, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "Text.") == 0);
+    rtc_string_free(out);
+
+    options.ruby = RTC_RUBY_PARENTHESIZED;
+    status = rtc_prepare_text("<p><ruby>\xE6\xBC\xA2<rt>\xE3\x81\x8B\xE3\x82\x93</rt></ruby></p>", &options, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "\xE6\xBC\xA2(\xE3\x81\x8B\xE3\x82\x93)") == 0);
+    rtc_string_free(out);
+
+    options.math_placeholder = "[MATH]";
+    status = rtc_prepare_text("<p>Solve <math><mi>x</mi></math> now.</p>", &options, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "Solve [MATH] now.") == 0);
+    rtc_string_free(out);
+}
+
+static void test_prepare_bytes(void) {
+    const uint8_t page[] = "<p>Caf\xE9 cr\xE8me.</p>";
+    char *out = NULL;
+    RtcStatus status = rtc_prepare_bytes(page, sizeof(page) - 1, "text/html; charset=windows-1252", NULL, &out);
+    assert(status == RTC_STATUS_OK);
+    assert(strcmp(out, "Caf\xC3\xA9 cr\xC3\xA8me.") == 0);
+    rtc_string_free(out);
+}
+
+static void test_errors(void) {
+    char *out = (char *) 1;
+    assert(rtc_prepare_text(NULL, NULL, &out) == RTC_STATUS_NULL_ARGUMENT);
+    assert(out == NULL);
+    assert(strcmp(rtc_last_error_message(), "`html` is NULL") == 0);
+
+    assert(rtc_prepare_text("<p>\xFF</p>", NULL, &out) == RTC_STATUS_INVALID_UTF8);
+    assert(out == NULL);
+    assert(rtc_last_error_message() != NULL);
+
+    assert(rtc_prepare_text("<p>text</p>", NULL, NULL) == RTC_STATUS_NULL_ARGUMENT);
+
+    /* freeing NULL is allowed */
+    rtc_string_free(NULL);
+}
+
+int main(void) {
+    test_prepare_text();
+    test_options();
+    test_prepare_bytes();
+    test_errors();
+    printf("C API tests passed\n");
+    return 0;
+}
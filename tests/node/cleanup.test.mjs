@@ -0,0 +1,148 @@
+// Tests for the wasm build, run with `make wasm-test` (Node.js 18+)
+import assert from 'node:assert/strict';
+import { readdirSync, readFileSync } from 'node:fs';
+import { createRequire } from 'node:module';
+import { test } from 'node:test';
+
+const require = createRequire(import.meta.url);
+const rtc = require(process.env.RTC_PKG ?? '../../pkg/readability_text_cleanup.js');
+
+// shared with the Rust and Python test suites
+const fixturesDir = new URL('../fixtures/', import.meta.url);
+const fixtures = readdirSync(fixturesDir)
+    .filter((name) => name.endsWith('.html'))
+    .sort()
+    .map((name) => ({
+        name,
+        html: readFileSync(new URL(name, fixturesDir), 'utf8'),
+        expected: readFileSync(new URL(name.replace(/\.html$/, '.txt'), fixturesDir), 'utf8').trimEnd(),
+    }));
+
+test('fixtures match the Rust output', () => {
+    assert.ok(fixtures.length > 0);
+    for (const { name, html, expected } of fixtures) {
+        assert.equal(rtc.prepare_text(html), expected, name);
+        assert.equal(rtc.prepareTextWithOptions(html), expected, name);
+        assert.equal(rtc.prepareDocument(html).text, expected, name);
+    }
+});
+
+test('prepareDocument returns plain objects with UTF-16 offsets', () => {
+    const html = '<html lang="de"><head><meta name="author" content="Anna"></head>'
+        + '<body><h1>Überschrift 😀</h1><p>Erster Satz hier. Zweiter Satz 😀 dort!</p></body></html>';
+    const doc = rtc.prepareDocument(html);
+
+    assert.equal(Object.getPrototypeOf(doc), Object.prototype);
+    assert.ok(Array.isArray(doc.paragraphs));
+    for (const paragraph of doc.paragraphs) {
+        for (const sentence of paragraph.sentences) {
+            assert.equal(doc.text.slice(sentence.start, sentence.end), sentence.text);
+        }
+    }
+    assert.deepEqual(doc.paragraphs.at(-1).sentences.map((s) => s.text), ['Erster Satz hier.', 'Zweiter Satz 😀 dort!']);
+    assert.equal(doc.metadata.language, 'de');
+    assert.equal(doc.metadata.byline, 'Anna');
+    assert.equal(doc.metadata.siteName, null);
+    assert.deepEqual(doc.metadata.headings, [{ level: 1, text: 'Überschrift 😀' }]);
+    assert.equal(doc.stats.paragraphs, 2);
+    assert.equal(doc.stats.sentences, 3);
+});
+
+test('options object', () => {
+    const html = '<p>See here[1].</p>';
+    assert.equal(rtc.prepareTextWithOptions(html, { stripCitations: false }), 'See here[1].');
+    assert.equal(rtc.prepareTextWithOptions(html, {}), 'See here.');
+    assert.equal(rtc.prepareTextWithOptions(html, undefined), 'See here.');
+    assert.throws(() => rtc.prepareTextWithOptions(html, { stripCitations: 'no' }));
+    assert.equal(rtc.prepareTextWithOptions('<nav>Home</nav><p>Text.</p>', { dropBoilerplate: true }), 'Text.');
+    const figure = '<figure><img src="chart.png"><figcaption>Sales.</figcaption></figure><p>Text.</p>';
+    assert.equal(rtc.prepareTextWithOptions(figure, { captions: false }), 'Text.');
+    assert.equal(rtc.prepareTextWithOptions('<p><ruby>漢字<rt>かんじ</rt></ruby></p>', { ruby: 'reading' }), 'かんじ');
+    assert.equal(rtc.prepareTextWithOptions('<p>Solve <math><mi>x</mi></math> now.</p>', { mathPlaceholder: '[MATH]' }), 'Solve [MATH] now.');
+});
+
+test('splitSentences and extractMetadata', () => {
+    assert.deepEqual(rtc.splitSentences('<p>First sentence. Second one!</p><p>Third?</p>'), [
+        ['First sentence.', 'Second one!'],
+        ['Third?'],
+    ]);
+    assert.equal(rtc.extractMetadata('<title>Hello</title>').title, 'Hello');
+});
+
+test('readability scores', () => {
+    const scores = rtc.readability('<p>The cat sat on the mat. It was happy.</p>');
+    assert.equal(scores.stats.sentences, 2);
+    assert.equal(scores.stats.words, 9);
+    assert.ok(scores.fleschReadingEase > 90);
+    assert.deepEqual(Object.keys(rtc.readability('')), Object.keys(scores));
+    assert.equal(scores.language, 'en');
+
+    const german = rtc.readability('<html lang="de"><p>Der Hund ist nicht im Haus.</p></html>');
+    assert.equal(german.language, 'de');
+    assert.deepEqual(german.languageScores.map((score) => score.formula), ['amstad', 'wienerSachtextformel', 'lix', 'rix']);
+});
+
+test('statistics and reading rates', () => {
+    const stats = rtc.statistics('<p>Four words in here.</p>');
+    assert.equal(stats.language, 'en');
+    assert.equal(stats.words, 4);
+    assert.equal(stats.readingMinutes, 4 / 228);
+    assert.equal(rtc.statistics('<p>Four words in here.</p>', undefined, { reading: { en: 2 } }).readingMinutes, 2);
+    assert.equal(rtc.statistics('<p>東京は大きい都市です。</p>').words, 10);
+});
+
+test('analyze', () => {
+    const analysis = rtc.analyze('<p>😀 It was quickly written.</p>', undefined, { qualifiers: [] });
+    const spans = analysis.sentences[0].annotations.map((a) => [a.category, analysis.text.slice(a.start, a.end)]);
+    assert.deepEqual(spans, [['passive', 'was quickly written'], ['adverb', 'quickly']]);
+    assert.equal(rtc.analyze('<p>Utilize it.</p>').sentences[0].annotations[0].suggestion, 'use');
+});
+
+test('summarize', () => {
+    const html = '<p>Solar power capacity grew quickly. Solar panels are cheap.</p><p>The weather was mild.</p>';
+    const summary = rtc.summarize(html, undefined, { algorithm: 'lexRank', length: { sentences: 2 } });
+    assert.deepEqual(summary.sentences.map((s) => s.index), [0, 1]);
+    assert.equal(summary.text, 'Solar power capacity grew quickly. Solar panels are cheap.');
+    assert.deepEqual(rtc.summarize(html, undefined, { length: { words: 1 } }).sentences, []);
+});
+
+test('keywords', () => {
+    const html = '<h2>Coffee</h2><p>Green tea is popular. Strong coffee is popular. Green tea is cheap. Strong coffee is cheap.</p>';
+    const text = rtc.prepare_text(html);
+    const [best] = rtc.keywords(html, undefined, { limit: 1, headingBoost: 1 });
+    assert.equal(best.phrase, 'strong coffee');
+    assert.equal(best.occurrences.length, 2);
+    assert.equal(text.slice(best.occurrences[0].start, best.occurrences[0].end), 'Strong coffee');
+    assert.equal(rtc.keywords(html, undefined, { algorithm: 'yake', limit: 3 }).length, 3);
+});
+
+test('extractTables', () => {
+    const html = '<table><caption>Scores</caption><tr><th>Team</th><th>Points</th></tr><tr><td rowspan="2">Lions, "A"</td><td>3</td></tr><tr><td>1</td></tr></table>';
+    assert.deepEqual(rtc.extractTables(html), [{
+        caption: 'Scores',
+        header: [['Team', 'Points']],
+        body: [['Lions, "A"', '3'], ['Lions, "A"', '1']],
+    }]);
+    assert.deepEqual(rtc.extractTablesCsv(html), ['Team,Points\r\n"Lions, ""A""",3\r\n"Lions, ""A""",1\r\n']);
+});
+
+test('extractImages', () => {
+    const html = '<img src="/pixel.gif"><figure><img data-src="/photo.jpg" srcset="/photo-2x.jpg 2x" alt="Photo" width="800"><figcaption>Harbour</figcaption></figure>';
+    assert.deepEqual(rtc.extractImages(html), [{
+        url: '/photo-2x.jpg',
+        alt: 'Photo',
+        caption: 'Harbour',
+        width: 800,
+        height: null,
+        lead: true,
+    }]);
+});
+
+test('extractLinks', () => {
+    const html = '<nav><a href="/">Home</a></nav><main><a href="../docs" rel="nofollow">Docs</a></main>';
+    assert.deepEqual(rtc.extractLinks(html, 'https://example.com/blog/post'), [
+        { text: 'Home', url: 'https://example.com/', rel: [], inContent: false },
+        { text: 'Docs', url: 'https://example.com/docs', rel: ['nofollow'], inContent: true },
+    ]);
+    assert.equal(rtc.extractLinks(html)[1].url, '../docs');
+});